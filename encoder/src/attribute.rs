@@ -1,7 +1,13 @@
 #![allow(non_camel_case_types)]
 
 use self::sealed::Sealed;
-use crate::{batch::Batch, InNamespace};
+use crate::{
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+    extended::{MathMlAttribute, SvgAttribute},
+    prefixed::PrefixedAttribute,
+    InNamespace,
+};
 
 mod sealed {
     use crate::{Attribute, InNamespace};
@@ -9,6 +15,10 @@ mod sealed {
     pub trait Sealed {}
 
     impl Sealed for Attribute {}
+    impl Sealed for crate::attribute::CustomAttribute {}
+    impl Sealed for crate::extended::SvgAttribute {}
+    impl Sealed for crate::extended::MathMlAttribute {}
+    impl<'a> Sealed for crate::prefixed::PrefixedAttribute<'a> {}
     impl<'a> Sealed for InNamespace<'a, Attribute> {}
     impl<'a> Sealed for &'a str {}
     impl<'a, 'b> Sealed for InNamespace<'b, &'a str> {}
@@ -17,15 +27,26 @@ mod sealed {
 #[derive(Clone, Copy)]
 pub enum AnyAttribute<'a, 'b> {
     Attribute(Attribute),
+    Custom(CustomAttribute),
+    Svg(SvgAttribute),
+    MathMl(MathMlAttribute),
+    Prefixed(PrefixedAttribute<'a>),
     InNamespace(InNamespace<'a, Attribute>),
     Str(&'a str),
     InNamespaceStr(InNamespace<'a, &'b str>),
 }
 
 impl AnyAttribute<'_, '_> {
-    pub(crate) unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch) {
+    pub(crate) unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
         match self {
             AnyAttribute::Attribute(a) => a.encode_u8_discriminant_prealloc(v),
+            AnyAttribute::Custom(a) => a.encode_u8_discriminant_prealloc(v),
+            AnyAttribute::Svg(a) => a.encode_u8_discriminant_prealloc(v),
+            AnyAttribute::MathMl(a) => a.encode_u8_discriminant_prealloc(v),
+            AnyAttribute::Prefixed(a) => a.encode_u8_discriminant_prealloc(v),
             AnyAttribute::InNamespace(a) => a.encode_u8_discriminant_prealloc(v),
             AnyAttribute::Str(a) => a.encode_u8_discriminant_prealloc(v),
             AnyAttribute::InNamespaceStr(a) => a.encode_u8_discriminant_prealloc(v),
@@ -34,10 +55,11 @@ impl AnyAttribute<'_, '_> {
 
     pub(crate) fn size_with_u8_discriminant(&self) -> usize {
         match self {
-            AnyAttribute::Attribute(_) => 1,
-            AnyAttribute::InNamespace(_) => 1 + 1 + 2,
-            AnyAttribute::Str(_) => 1 + 2,
-            AnyAttribute::InNamespaceStr(_) => 1 + 2 + 2,
+            AnyAttribute::Attribute(_) | AnyAttribute::Custom(_) => 1,
+            AnyAttribute::Svg(_) | AnyAttribute::MathMl(_) => 2,
+            AnyAttribute::InNamespace(a) => 1 + 1 + a.1.size(),
+            AnyAttribute::Prefixed(_) | AnyAttribute::Str(_) => 1 + 2,
+            AnyAttribute::InNamespaceStr(a) => 1 + 2 + a.1.size(),
         }
     }
 }
@@ -48,18 +70,18 @@ pub trait IntoAttribue<'a, 'b>: Sealed + Into<AnyAttribute<'a, 'b>> {
     const SINGLE_BYTE: bool = false;
 
     /// Encode the attribute into the message channel
-    fn encode(self, v: &mut Batch);
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull>;
 
     /// Encode the attribute into the message channel with memory pre-allocated
     ///
     /// # Safety
     ///
     /// This is only safe if the batch is preallocated to the correct size
-    unsafe fn encode_prealloc(self, v: &mut Batch)
+    unsafe fn encode_prealloc<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull>
     where
         Self: Sized,
     {
-        self.encode(v);
+        self.encode(v)
     }
 
     /// Encode the attribute into the message channel with a u8 desciminant instead of bit packed bools
@@ -67,7 +89,10 @@ pub trait IntoAttribue<'a, 'b>: Sealed + Into<AnyAttribute<'a, 'b>> {
     /// # Safety
     ///
     /// This is only safe if the batch is preallocated to the correct size
-    unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch);
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull>;
 }
 
 impl<'a, 'b> Attribute {
@@ -81,26 +106,29 @@ impl<'a, 'b> IntoAttribue<'a, 'b> for Attribute {
     const SINGLE_BYTE: bool = true;
 
     #[inline(always)]
-    fn encode(self, v: &mut Batch) {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         v.encode_bool(false);
         v.encode_bool(false);
-        v.msg.push(self as u8);
+        v.msg.try_push(self as u8)
     }
 
     #[inline(always)]
-    unsafe fn encode_prealloc(self, v: &mut Batch) {
+    unsafe fn encode_prealloc<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         v.encode_bool(false);
         v.encode_bool(false);
         unsafe {
-            let ptr: *mut u8 = v.msg.as_mut_ptr();
-            *ptr.add(v.msg.len()) = self as u8;
-            v.msg.set_len(v.msg.len() + 1);
+            v.encode_u8_prealloc(self as u8);
         }
+        Ok(())
     }
 
     #[inline(always)]
-    unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch) {
-        v.encode_u8_prealloc(self as u8)
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
+        v.encode_u8_prealloc(self as u8);
+        Ok(())
     }
 }
 
@@ -110,6 +138,53 @@ impl<'a, 'b> From<Attribute> for AnyAttribute<'a, 'b> {
     }
 }
 
+/// An application specific attribute name that is encoded with a single byte like an [`Attribute`]. Custom attributes are created with [`CustomNames::attribute`](crate::custom::CustomNames::attribute).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomAttribute(pub(crate) u8);
+
+impl CustomAttribute {
+    /// The byte the attribute is encoded as
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+}
+
+impl<'a, 'b> IntoAttribue<'a, 'b> for CustomAttribute {
+    const SINGLE_BYTE: bool = true;
+
+    #[inline(always)]
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(false);
+        v.encode_bool(false);
+        v.msg.try_push(self.0)
+    }
+
+    #[inline(always)]
+    unsafe fn encode_prealloc<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(false);
+        v.encode_bool(false);
+        unsafe {
+            v.encode_u8_prealloc(self.0);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
+        v.encode_u8_prealloc(self.0);
+        Ok(())
+    }
+}
+
+impl<'a, 'b> From<CustomAttribute> for AnyAttribute<'a, 'b> {
+    fn from(a: CustomAttribute) -> Self {
+        AnyAttribute::Custom(a)
+    }
+}
+
 impl<'a, 'b> InNamespace<'a, Attribute> {
     pub const fn any_attr_const(self) -> AnyAttribute<'a, 'b> {
         AnyAttribute::InNamespace(self)
@@ -118,18 +193,21 @@ impl<'a, 'b> InNamespace<'a, Attribute> {
 
 impl<'a, 'b> IntoAttribue<'a, 'b> for InNamespace<'a, Attribute> {
     #[inline(always)]
-    fn encode(self, v: &mut Batch) {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         v.encode_bool(false);
-        v.msg.push(self.0 as u8);
+        v.msg.try_push(self.0 as u8)?;
         v.encode_bool(true);
-        v.encode_str(self.1);
+        self.1.encode(v)
     }
 
     #[inline(always)]
-    unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch) {
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
         v.encode_u8_prealloc(255);
         v.encode_u8_prealloc(self.0 as u8);
-        v.encode_str_prealloc(self.1);
+        self.1.encode_prealloc(v)
     }
 }
 
@@ -140,15 +218,19 @@ impl<'a, 'b> From<InNamespace<'a, Attribute>> for AnyAttribute<'a, 'b> {
 }
 
 impl<'a, 'b> IntoAttribue<'a, 'b> for &'a str {
-    fn encode(self, v: &mut Batch) {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         v.encode_bool(true);
-        v.encode_cachable_str(self);
+        v.encode_cachable_str(self)?;
         v.encode_bool(false);
+        Ok(())
     }
 
-    unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch) {
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
         v.encode_u8_prealloc(254);
-        v.encode_str_prealloc(self);
+        v.encode_str_prealloc(self)
     }
 }
 
@@ -165,17 +247,20 @@ impl<'a, 'b> InNamespace<'a, &'b str> {
 }
 
 impl<'a, 'b> IntoAttribue<'a, 'b> for InNamespace<'a, &'b str> {
-    fn encode(self, v: &mut Batch) {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         v.encode_bool(true);
-        v.encode_cachable_str(self.0);
+        v.encode_cachable_str(self.0)?;
         v.encode_bool(true);
-        v.encode_cachable_str(self.1);
+        self.1.encode(v)
     }
 
-    unsafe fn encode_u8_discriminant_prealloc(self, v: &mut Batch) {
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
         v.encode_u8_prealloc(253);
-        v.encode_str_prealloc(self.0);
-        v.encode_str_prealloc(self.1);
+        v.encode_str_prealloc(self.0)?;
+        self.1.encode_prealloc(v)
     }
 }
 
@@ -185,86 +270,74 @@ impl<'a, 'b> From<InNamespace<'a, &'b str>> for AnyAttribute<'a, 'b> {
     }
 }
 
-macro_rules! attributes {
-    ($($i: ident),*) => {
-        /// All built-in attributes
-        /// These are the attributes can be encoded with a single byte so they are more efficient (but less flexable) than a &str attribute
-        #[derive(Copy, Clone)]
-        pub enum Attribute {
-            $(
-                $i
-            ),*
-        }
-
-        pub struct NotElementError;
-
-        impl std::str::FromStr for Attribute {
-            type Err = NotElementError;
-
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok(match s{
-                    $(
-                        stringify!($i) => Self::$i,
-                    )*
-                    _ => return Err(NotElementError)
-                })
-            }
-        }
-    };
-}
-
-attributes! {
-    accept_charset,
+name_table! {
+    /// All built-in attributes
+    /// These are the attributes can be encoded with a single byte so they are more efficient (but less flexable) than a &str attribute
+    pub enum Attribute;
+    /// The error returned when parsing a name that is not a built-in [`Attribute`]
+    pub struct ParseAttributeError("not a built-in attribute");
+    accept_charset = "accept-charset",
     accept,
     accesskey,
     action,
+    #[deprecated(note = "obsolete in the html standard")]
     align,
     allow,
     alt,
-    aria_atomic,
-    aria_busy,
-    aria_controls,
-    aria_current,
-    aria_describedby,
-    aria_description,
-    aria_details,
-    aria_disabled,
-    aria_dropeffect,
-    aria_errormessage,
-    aria_flowto,
-    aria_grabbed,
-    aria_haspopup,
-    aria_hidden,
-    aria_invalid,
-    aria_keyshortcuts,
-    aria_label,
-    aria_labelledby,
-    aria_live,
-    aria_owns,
-    aria_relevant,
-    aria_roledescription,
-    r#async,
+    aria_atomic = "aria-atomic",
+    aria_busy = "aria-busy",
+    aria_controls = "aria-controls",
+    aria_current = "aria-current",
+    aria_describedby = "aria-describedby",
+    aria_description = "aria-description",
+    aria_details = "aria-details",
+    aria_disabled = "aria-disabled",
+    #[deprecated(note = "obsolete in the html standard")]
+    aria_dropeffect = "aria-dropeffect",
+    aria_errormessage = "aria-errormessage",
+    aria_flowto = "aria-flowto",
+    #[deprecated(note = "obsolete in the html standard")]
+    aria_grabbed = "aria-grabbed",
+    aria_haspopup = "aria-haspopup",
+    aria_hidden = "aria-hidden",
+    aria_invalid = "aria-invalid",
+    aria_keyshortcuts = "aria-keyshortcuts",
+    aria_label = "aria-label",
+    aria_labelledby = "aria-labelledby",
+    aria_live = "aria-live",
+    aria_owns = "aria-owns",
+    aria_relevant = "aria-relevant",
+    aria_roledescription = "aria-roledescription",
+    r#async = "async",
     autocapitalize,
     autocomplete,
     autofocus,
     autoplay,
+    #[deprecated(note = "obsolete in the html standard")]
     background,
+    #[deprecated(note = "obsolete in the html standard")]
     bgcolor,
+    #[deprecated(note = "obsolete in the html standard")]
     border,
     buffered,
     capture,
+    #[deprecated(note = "obsolete in the html standard")]
     challenge,
     charset,
     checked,
     cite,
     class,
+    #[deprecated(note = "obsolete in the html standard")]
     code,
+    #[deprecated(note = "obsolete in the html standard")]
     codebase,
+    #[deprecated(note = "obsolete in the html standard")]
     color,
     cols,
     colspan,
     content,
     contenteditable,
+    #[deprecated(note = "obsolete in the html standard")]
     contextmenu,
     controls,
     coords,
@@ -282,7 +355,7 @@ attributes! {
     draggable,
     enctype,
     enterkeyhint,
-    r#for,
+    r#for = "for",
     form,
     formaction,
     formenctype,
@@ -295,24 +368,30 @@ attributes! {
     high,
     href,
     hreflang,
-    http_equiv,
+    http_equiv = "http-equiv",
+    #[deprecated(note = "obsolete in the html standard")]
     icon,
     id,
+    #[deprecated(note = "obsolete in the html standard")]
     importance,
     inputmode,
     integrity,
+    #[deprecated(note = "obsolete in the html standard")]
     intrinsicsize,
     ismap,
     itemprop,
+    #[deprecated(note = "obsolete in the html standard")]
     keytype,
     kind,
     label,
     lang,
+    #[deprecated(note = "obsolete in the html standard")]
     language,
     list,
     loading,
-    r#loop,
+    r#loop = "loop",
     low,
+    #[deprecated(note = "obsolete in the html standard")]
     manifest,
     max,
     maxlength,
@@ -331,6 +410,7 @@ attributes! {
     placeholder,
     poster,
     preload,
+    #[deprecated(note = "obsolete in the html standard")]
     radiogroup,
     readonly,
     referrerpolicy,
@@ -342,6 +422,7 @@ attributes! {
     rowspan,
     sandbox,
     scope,
+    #[deprecated(note = "obsolete in the html standard")]
     scoped,
     selected,
     shape,
@@ -357,14 +438,71 @@ attributes! {
     start,
     step,
     style,
+    #[deprecated(note = "obsolete in the html standard")]
     summary,
     tabindex,
     target,
     title,
     translate,
-    r#type,
+    r#type = "type",
     usemap,
     value,
     width,
-    wrap
+    wrap,
+    aria_activedescendant = "aria-activedescendant",
+    aria_autocomplete = "aria-autocomplete",
+    aria_checked = "aria-checked",
+    aria_colcount = "aria-colcount",
+    aria_colindex = "aria-colindex",
+    aria_colspan = "aria-colspan",
+    aria_expanded = "aria-expanded",
+    aria_level = "aria-level",
+    aria_modal = "aria-modal",
+    aria_multiline = "aria-multiline",
+    aria_multiselectable = "aria-multiselectable",
+    aria_orientation = "aria-orientation",
+    aria_placeholder = "aria-placeholder",
+    aria_posinset = "aria-posinset",
+    aria_pressed = "aria-pressed",
+    aria_readonly = "aria-readonly",
+    aria_required = "aria-required",
+    aria_rowcount = "aria-rowcount",
+    aria_rowindex = "aria-rowindex",
+    aria_rowspan = "aria-rowspan",
+    aria_selected = "aria-selected",
+    aria_setsize = "aria-setsize",
+    aria_sort = "aria-sort",
+    aria_valuemax = "aria-valuemax",
+    aria_valuemin = "aria-valuemin",
+    aria_valuenow = "aria-valuenow",
+    aria_valuetext = "aria-valuetext",
+    abbr,
+    r#as = "as",
+    blocking,
+    exportparts,
+    fetchpriority,
+    imagesizes,
+    imagesrcset,
+    inert,
+    is,
+    itemid,
+    itemref,
+    itemscope,
+    itemtype,
+    nomodule,
+    nonce,
+    part,
+    playsinline,
+    popover,
+    popovertarget,
+    popovertargetaction,
+    shadowrootmode,
+    aria_braillelabel = "aria-braillelabel",
+    aria_brailleroledescription = "aria-brailleroledescription",
+    aria_colindextext = "aria-colindextext",
+    aria_rowindextext = "aria-rowindextext",
 }
+
+/// The old name of [`ParseAttributeError`]
+#[deprecated(note = "use ParseAttributeError")]
+pub type NotElementError = ParseAttributeError;
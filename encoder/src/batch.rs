@@ -1,13 +1,20 @@
+use alloc::vec::Vec;
+
 use crate::{
-    ElementBuilder, IntoAttribue, IntoElement, MaybeId, NodeId, TextBuilder, WritableText,
+    attribute::AnyAttribute,
+    buffer::{Buffer, BufferFull},
+    optimize::string_lengths,
+    style::AnyStyle,
+    ElementBuilder, IntoAttribue, IntoElement, IntoStyle, MaybeId, NodeId, OwnedElementBuilder,
+    TextBuilder, WritableText,
 };
 
-// operations that have no booleans can be encoded as a half byte, these are placed first
+// every op is encoded in the low 5 bits of its byte and the upper 3 bits hold up to three bools. The 5-bit op space (codes 0-31) is full, so a new operation has to reuse an existing op with a bool to tell the forms apart
 pub enum Op {
-    /// Navigates to the last node to the first child of the current node.
+    /// Navigates to the last node to the first, last or nth child of the current node.
     FirstChild = 0,
 
-    /// Navigates to the last node to the last child of the current node.
+    /// Navigates to the last node to the next or previous sibling of the current node.
     NextSibling = 1,
 
     /// Navigates to the last node to the parent of the current node.
@@ -25,7 +32,7 @@ pub enum Op {
     /// Build Full Element
     BuildFullElement = 6,
 
-    /// Pop the topmost node from our stack and append them to the node
+    /// Append a node or the topmost nodes from the node stack to the node
     AppendChildren = 7,
 
     /// Replace a given (single) node with a handful of nodes currently on the stack.
@@ -61,40 +68,102 @@ pub enum Op {
     /// Remove a style property from a node.
     RemoveStyle = 18,
 
-    /// Clones a node.
+    /// Clones a node and its descendants or only the node.
     CloneNode = 19,
 
     /// Does nothing, but allows us to skip a byte.
     NoOp = 20,
+
+    /// Set a number of style properties on a node.
+    SetStyles = 21,
+
+    /// Set the css text of a node's style.
+    SetCssText = 22,
+
+    /// Add, remove or toggle a boolean attribute.
+    ToggleAttribute = 23,
+
+    /// Set many attributes on a node.
+    SetAttributes = 24,
+
+    /// Set one attribute on many nodes.
+    SetAttributeMany = 25,
+
+    /// Set or remove a property of a node's dataset.
+    Dataset = 26,
+
+    /// Append, insert or delete text in a text node.
+    EditText = 27,
+
+    /// Push a node onto the node stack.
+    PushRoot = 28,
+
+    /// Walk a path of child indices from the last node, or store the nodes at many paths from a node with ids.
+    Navigate = 29,
+
+    /// Save the last node on the cursor stack or restore it from the cursor stack.
+    Cursor = 30,
+
+    /// Clones a node and stores the nodes at paths in the clone with ids.
+    CloneNodeWithIds = 31,
+}
+
+const _: () = assert!(
+    Op::CloneNodeWithIds as u8 <= 0x1F,
+    "ops must fit in the low 5 bits of the op byte"
+);
+
+/// How the strings of a batch are encoded in the string buffer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StringEncoding {
+    /// Strings are encoded as utf-8 and the interpreter decodes them with a `TextDecoder`. String lengths are the number of bytes.
+    #[default]
+    Utf8,
+    /// Strings are encoded as little-endian utf-16 code units that the interpreter can read with `String.fromCharCode` without decoding them. String lengths are the number of code units.
+    ///
+    /// This makes the string buffer up to twice as large for ascii text, but avoids the constant cost of `TextDecoder.decode`.
+    Utf16,
 }
 
 /// A batch of operations ready to perform on the DOM.
 pub trait PreparedBatch {
     fn msg(&self) -> &[u8];
     fn str(&self) -> &[u8];
+
+    /// How the strings in [`PreparedBatch::str`] are encoded
+    fn string_encoding(&self) -> StringEncoding {
+        StringEncoding::Utf8
+    }
 }
 
 /// A batch of operations ready to perform on the DOM.
-pub struct FinalizedBatch {
-    pub msg: Vec<u8>,
-    pub str: Vec<u8>,
+pub struct FinalizedBatch<B = Vec<u8>> {
+    pub msg: B,
+    pub str: B,
+    pub string_encoding: StringEncoding,
 }
 
-impl PreparedBatch for FinalizedBatch {
+impl<B: Buffer> PreparedBatch for FinalizedBatch<B> {
     fn msg(&self) -> &[u8] {
-        &self.msg
+        self.msg.as_slice()
     }
     fn str(&self) -> &[u8] {
-        &self.str
+        self.str.as_slice()
+    }
+    fn string_encoding(&self) -> StringEncoding {
+        self.string_encoding
     }
 }
 
-impl<'a> PreparedBatch for &'a FinalizedBatch {
+impl<'a, B: Buffer> PreparedBatch for &'a FinalizedBatch<B> {
     fn msg(&self) -> &[u8] {
-        &self.msg
+        self.msg.as_slice()
     }
     fn str(&self) -> &[u8] {
-        &self.str
+        self.str.as_slice()
+    }
+    fn string_encoding(&self) -> StringEncoding {
+        self.string_encoding
     }
 }
 
@@ -129,175 +198,415 @@ impl<'a> PreparedBatch for &'a StaticBatch {
 /// This is useful for building up a batch of operations to perform on the DOM many times. If the operation is only performed once, it is better to use the `MsgChannel` directly because it reuses the same allocation from the last batch of operations.
 /// See [`MsgChannel::append`] and [`MsgChannel::run_batch`] for examples.
 /// The methods on this struct are a subset of the methods on [`MsgChannel`] and work the same with the exception of [`Batch::finalize`].
-pub struct Batch {
+///
+/// Batches are always encoded as little-endian bytes without any unaligned writes, so a batch encoded on any host (for example a server) is byte-for-byte the same as a batch encoded in wasm.
+///
+/// By default a batch is encoded into a [`Vec<u8>`], but it can encode into any [`Buffer`] with [`Batch::with_buffers`]. If a buffer cannot fit an operation, the operation is not encoded and the batch is marked as full (see [`Batch::is_full`]). Every operation encoded before the batch became full is kept and can still be finalized.
+pub struct Batch<B: Buffer = Vec<u8>> {
     #[doc(hidden)]
-    pub msg: Vec<u8>,
+    pub msg: B,
     #[doc(hidden)]
-    pub str_buf: Vec<u8>,
+    pub str_buf: B,
     #[doc(hidden)]
     pub current_op_batch_idx: usize,
     #[doc(hidden)]
     pub current_op_byte_idx: usize,
     #[doc(hidden)]
     pub current_op_bit_pack_index: u8,
+    pub(crate) full: bool,
+    string_encoding: StringEncoding,
 }
 
 impl Default for Batch {
     fn default() -> Self {
+        Self::with_buffers(Vec::new(), Vec::new())
+    }
+}
+
+impl<B: Buffer> Batch<B> {
+    /// Create a new batch that encodes the operations into `msg` and the strings into `str_buf`. Both buffers are cleared.
+    pub fn with_buffers(mut msg: B, mut str_buf: B) -> Self {
+        msg.clear();
+        str_buf.clear();
         Self {
-            msg: Vec::new(),
-            str_buf: Vec::new(),
+            msg,
+            str_buf,
             current_op_byte_idx: 3,
             current_op_bit_pack_index: 0,
             current_op_batch_idx: 0,
+            full: false,
+            string_encoding: StringEncoding::Utf8,
         }
     }
-}
 
-impl Batch {
+    /// How strings are encoded in the batch
+    pub fn string_encoding(&self) -> StringEncoding {
+        self.string_encoding
+    }
+
+    /// Change how strings are encoded in the batch. The encoding is kept when the batch is cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch already contains strings
+    pub fn set_string_encoding(&mut self, encoding: StringEncoding) {
+        assert!(
+            self.str_buf.is_empty(),
+            "the string encoding can only be changed before any strings are encoded"
+        );
+        self.string_encoding = encoding;
+    }
+
+    /// If an operation did not fit in the buffers. Once a batch is full no more operations are encoded until the batch is cleared.
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Remove all of the operations from the batch, keeping the buffers.
+    pub fn clear(&mut self) {
+        self.msg.clear();
+        self.str_buf.clear();
+        self.current_op_batch_idx = 0;
+        self.current_op_byte_idx = 3;
+        self.current_op_bit_pack_index = 0;
+        self.full = false;
+    }
+
     /// Finalizes the batch and prepares it to be run
-    pub fn finalize(mut self) -> FinalizedBatch {
-        self.encode_op(Op::Stop);
-        FinalizedBatch {
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message buffer cannot fit the final stop operation. This can only happen if the buffer is smaller than four bytes, see [`Batch::try_finalize`].
+    pub fn finalize(self) -> FinalizedBatch<B> {
+        self.try_finalize()
+            .expect("the message buffer is too small to finalize the batch")
+    }
+
+    /// Finalizes the batch and prepares it to be run, or returns [`BufferFull`] if the message buffer cannot fit the final stop operation.
+    pub fn try_finalize(mut self) -> Result<FinalizedBatch<B>, BufferFull> {
+        self.try_encode_op(Op::Stop)?;
+        Ok(FinalizedBatch {
             msg: self.msg,
             str: self.str_buf,
-        }
+            string_encoding: self.string_encoding,
+        })
     }
 
     /// Appends a number of nodes as children of the given node.
     pub fn append_child(&mut self, root: MaybeId, child: MaybeId) {
-        self.encode_op(Op::AppendChildren);
-        let size = root.encoded_size() + child.encoded_size();
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(root);
-            self.encode_maybe_id_prealloc(child);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::AppendChildren)?;
+            let size = root.encoded_size() + child.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                b.encode_maybe_id_prealloc(child);
+            }
+            Ok(())
+        })
+    }
+
+    /// Push a node onto the node stack. Nodes on the stack are consumed by [`Batch::append_children`], [`Batch::replace_with_stack`], [`Batch::insert_stack_after`] and [`Batch::insert_stack_before`].
+    pub fn push_root(&mut self, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::PushRoot)?;
+            b.msg.try_reserve(id.encoded_size() as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+            }
+            Ok(())
+        })
+    }
+
+    /// Pops the topmost `n` nodes from the node stack and appends them to the given node in the order they were pushed.
+    pub fn append_children(&mut self, root: MaybeId, n: u16) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::AppendChildren)?;
+            b.msg.try_reserve(root.encoded_size() as usize + 2)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                b.encode_bool(false);
+                b.encode_bool(true);
+                b.encode_u16_prealloc(n);
+            }
+            Ok(())
+        })
+    }
+
+    /// Encodes an insert or replace operation that uses the topmost `n` nodes from the node stack
+    fn encode_stack_insert(&mut self, op: Op, root: MaybeId, n: u16) {
+        self.encode_with(|b| {
+            b.try_encode_op(op)?;
+            b.msg.try_reserve(root.encoded_size() as usize + 2)?;
+            unsafe {
+                b.encode_bool(true);
+                b.encode_maybe_id_prealloc(root);
+                b.encode_bool(true);
+                b.encode_u16_prealloc(n);
+            }
+            Ok(())
+        })
     }
 
     /// Replace a node with another node
     pub fn replace_with(&mut self, root: MaybeId, node: MaybeId) {
-        self.encode_op(Op::ReplaceWith);
-        let size = root.encoded_size() + node.encoded_size();
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(root);
-            self.encode_maybe_id_prealloc(node);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::ReplaceWith)?;
+            let size = root.encoded_size() + node.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_bool(false);
+                b.encode_maybe_id_prealloc(root);
+                b.encode_maybe_id_prealloc(node);
+            }
+            Ok(())
+        })
     }
 
     /// Replace a node with many nodes
     pub fn replace_with_nodes(&mut self, root: MaybeId, nodes: &[MaybeId]) {
-        self.encode_op(Op::ReplaceWith);
-        self.encode_bool(true);
-        self.encode_maybe_id(root);
-        self.msg.push(nodes.len() as u8);
-        for n in nodes {
-            self.encode_maybe_id_u8_discriminant(*n);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::ReplaceWith)?;
+            b.encode_bool(true);
+            b.encode_maybe_id(root)?;
+            b.encode_maybe_ids_u8_discriminant(nodes)
+        })
+    }
+
+    /// Pops the topmost `n` nodes from the node stack and replaces a node with them in the order they were pushed.
+    pub fn replace_with_stack(&mut self, root: MaybeId, n: u16) {
+        self.encode_stack_insert(Op::ReplaceWith, root, n)
     }
 
     /// Insert a single node after a given node.
     pub fn insert_after(&mut self, root: MaybeId, node: MaybeId) {
-        self.encode_op(Op::InsertAfter);
-        let size = root.encoded_size() + node.encoded_size();
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_bool(false);
-            self.encode_maybe_id_prealloc(root);
-            self.encode_maybe_id_prealloc(node);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::InsertAfter)?;
+            let size = root.encoded_size() + node.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_bool(false);
+                b.encode_maybe_id_prealloc(root);
+                b.encode_maybe_id_prealloc(node);
+            }
+            Ok(())
+        })
     }
 
     /// Insert many nodes after a given node.
     pub fn insert_nodes_after(&mut self, root: MaybeId, nodes: &[MaybeId]) {
-        self.encode_op(Op::InsertAfter);
-        self.encode_bool(true);
-        self.encode_maybe_id(root);
-        self.msg.push(nodes.len() as u8);
-        for n in nodes {
-            self.encode_maybe_id_u8_discriminant(*n);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::InsertAfter)?;
+            b.encode_bool(true);
+            b.encode_maybe_id(root)?;
+            b.encode_maybe_ids_u8_discriminant(nodes)
+        })
+    }
+
+    /// Pops the topmost `n` nodes from the node stack and inserts them after a given node in the order they were pushed.
+    pub fn insert_stack_after(&mut self, root: MaybeId, n: u16) {
+        self.encode_stack_insert(Op::InsertAfter, root, n)
     }
 
     /// Insert a single node before a given node.
     pub fn insert_before(&mut self, root: MaybeId, node: MaybeId) {
-        self.encode_op(Op::InsertBefore);
-        let size = root.encoded_size() + node.encoded_size();
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_bool(false);
-            self.encode_maybe_id_prealloc(root);
-            self.encode_maybe_id_prealloc(node);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::InsertBefore)?;
+            let size = root.encoded_size() + node.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_bool(false);
+                b.encode_maybe_id_prealloc(root);
+                b.encode_maybe_id_prealloc(node);
+            }
+            Ok(())
+        })
     }
 
     /// Insert many nodes before a given node.
     pub fn insert_nodes_before(&mut self, root: MaybeId, nodes: &[MaybeId]) {
-        self.encode_op(Op::InsertBefore);
-        self.encode_bool(true);
-        self.encode_maybe_id(root);
-        self.msg.push(nodes.len() as u8);
-        for n in nodes {
-            self.encode_maybe_id_u8_discriminant(*n);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::InsertBefore)?;
+            b.encode_bool(true);
+            b.encode_maybe_id(root)?;
+            b.encode_maybe_ids_u8_discriminant(nodes)
+        })
+    }
+
+    /// Pops the topmost `n` nodes from the node stack and inserts them before a given node in the order they were pushed.
+    pub fn insert_stack_before(&mut self, root: MaybeId, n: u16) {
+        self.encode_stack_insert(Op::InsertBefore, root, n)
     }
 
     /// Remove a node from the DOM.
     pub fn remove(&mut self, id: MaybeId) {
-        self.encode_op(Op::Remove);
-        self.encode_maybe_id(id);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Remove)?;
+            b.encode_maybe_id(id)
+        })
     }
 
     /// Create a new text node
     pub fn create_text_node(&mut self, text: impl WritableText, id: Option<NodeId>) {
-        self.encode_op(Op::CreateTextNode);
-        let size = (id.is_some() as u8) * 4 + 2;
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_str_prealloc(text);
-            self.encode_optional_id_prealloc(id);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::CreateTextNode)?;
+            let size = (id.is_some() as u8) * 4 + 2;
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_str_prealloc(text)?;
+                b.encode_optional_id_prealloc(id);
+            }
+            Ok(())
+        })
+    }
+
+    /// Create a text node from a static string without copying the text into the batch.
+    ///
+    /// In wasm the batch only stores the location of the string, and the interpreter reads the text directly from wasm memory. This avoids copying large static text blocks and supports text longer than [`u16::MAX`] bytes.
+    /// A batch can only reference memory it is run in, so on other targets the text is copied like in [`Batch::create_text_node`].
+    pub fn create_static_text_node(&mut self, text: &'static str, id: Option<NodeId>) {
+        if cfg!(target_arch = "wasm32") {
+            self.create_text_node_in_memory(text, id)
+        } else {
+            self.create_text_node(text, id)
         }
     }
 
+    /// Create a text node that references the location of the string
+    pub(crate) fn create_text_node_in_memory(&mut self, text: &'static str, id: Option<NodeId>) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::CreateTextNode)?;
+            let size = (id.is_some() as u8) * 4 + 8;
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_static_str_prealloc(text);
+                b.encode_optional_id_prealloc(id);
+            }
+            b.encode_bool(true);
+            Ok(())
+        })
+    }
+
     /// Create a new element node
     pub fn create_element<'a, 'b, E>(&mut self, tag: E, id: Option<NodeId>)
     where
         E: IntoElement<'a, 'b>,
     {
-        self.encode_op(Op::CreateElement);
-        self.msg
-            .reserve((E::SINGLE_BYTE as u8 + (id.is_some() as u8) * 4) as usize);
-        unsafe {
-            tag.encode_prealloc(self);
-            self.encode_optional_id_prealloc(id);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::CreateElement)?;
+            b.msg.try_reserve(E::SINGLE_BYTE as usize)?;
+            unsafe {
+                tag.encode_prealloc(b)?;
+            }
+            // elements that are not a single byte reserve their own space, so the id is reserved after the element is encoded
+            b.msg.try_reserve((id.is_some() as usize) * 4)?;
+            unsafe {
+                b.encode_optional_id_prealloc(id);
+            }
+            Ok(())
+        })
     }
 
     /// Set the textcontent of a node.
     pub fn set_text(&mut self, text: impl WritableText, root: MaybeId) {
-        self.encode_op(Op::SetText);
-        let size = root.encoded_size() + 2;
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(root);
-            self.encode_str_prealloc(text);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetText)?;
+            let size = root.encoded_size() + 2;
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                b.encode_str_prealloc(text)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Append text to the end of a text node. Unlike [`Batch::set_text`] only the new text is sent.
+    pub fn append_text(&mut self, text: impl WritableText, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::EditText)?;
+            b.msg.try_reserve(id.encoded_size() as usize + 2)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_bool(false);
+                b.encode_bool(false);
+                b.encode_str_prealloc(text)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Insert text into a text node at an offset. The offset is in utf-16 code units like the offsets of `CharacterData.insertData`.
+    pub fn insert_text(&mut self, offset: u32, text: impl WritableText, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::EditText)?;
+            b.msg.try_reserve(id.encoded_size() as usize + 4 + 2)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_bool(true);
+                b.encode_bool(false);
+                b.encode_u32_prealloc(offset);
+                b.encode_str_prealloc(text)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Delete `count` characters from a text node starting at an offset. The offset and count are in utf-16 code units like the arguments of `CharacterData.deleteData`.
+    pub fn delete_text(&mut self, offset: u32, count: u32, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::EditText)?;
+            b.msg.try_reserve(id.encoded_size() as usize + 4 + 4)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_bool(false);
+                b.encode_bool(true);
+                b.encode_u32_prealloc(offset);
+                b.encode_u32_prealloc(count);
+            }
+            Ok(())
+        })
+    }
+
+    /// Set the text content of a node to a static string without copying the text into the batch. See [`Batch::create_static_text_node`] for how the text is read.
+    pub fn set_static_text(&mut self, text: &'static str, root: MaybeId) {
+        if cfg!(target_arch = "wasm32") {
+            self.set_text_in_memory(text, root)
+        } else {
+            self.set_text(text, root)
         }
     }
 
+    /// Set the text content of a node to the string at a location
+    pub(crate) fn set_text_in_memory(&mut self, text: &'static str, root: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetText)?;
+            let size = root.encoded_size() + 8;
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                b.encode_static_str_prealloc(text);
+            }
+            b.encode_bool(true);
+            Ok(())
+        })
+    }
+
     /// Set the value of a node's attribute.
     pub fn set_attribute<'a, 'b, A>(&mut self, attr: A, value: impl WritableText, root: MaybeId)
     where
         A: IntoAttribue<'a, 'b>,
     {
-        self.encode_op(Op::SetAttribute);
-        self.msg
-            .reserve((A::SINGLE_BYTE as u8 + root.encoded_size() + 2) as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(root);
-            attr.encode_prealloc(self);
-            self.encode_str_prealloc(value);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetAttribute)?;
+            b.msg
+                .try_reserve((A::SINGLE_BYTE as u8 + root.encoded_size()) as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                attr.encode_prealloc(b)?;
+            }
+            b.encode_str(value)
+        })
     }
 
     /// Remove an attribute from a node.
@@ -305,55 +614,205 @@ impl Batch {
     where
         A: IntoAttribue<'a, 'b>,
     {
-        self.encode_op(Op::RemoveAttribute);
-        let size = A::SINGLE_BYTE as u8 + root.encoded_size();
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(root);
-            attr.encode_prealloc(self);
-        }
+        self.encode_with(|b| {
+            b.try_encode_op(Op::RemoveAttribute)?;
+            let size = A::SINGLE_BYTE as u8 + root.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(root);
+                attr.encode_prealloc(b)
+            }
+        })
     }
 
     /// Clone a node and store it with a new id.
     pub fn clone_node(&mut self, id: MaybeId, new_id: MaybeId) {
-        self.encode_op(Op::CloneNode);
-        let size = id.encoded_size() + new_id.encoded_size();
-        self.msg.reserve(size as usize);
-        self.encode_maybe_id(id);
-        self.encode_maybe_id(new_id);
+        self.encode_clone_node(id, new_id, true)
+    }
+
+    /// Clone a node without its children and store it with a new id.
+    pub fn clone_node_shallow(&mut self, id: MaybeId, new_id: MaybeId) {
+        self.encode_clone_node(id, new_id, false)
+    }
+
+    fn encode_clone_node(&mut self, id: MaybeId, new_id: MaybeId, deep: bool) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::CloneNode)?;
+            let size = id.encoded_size() + new_id.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_maybe_id_prealloc(new_id);
+            }
+            b.encode_bool(!deep);
+            Ok(())
+        })
+    }
+
+    /// Clone a node and store the nodes at paths of child indices in the clone with ids. An empty path stores the clone itself. The clone becomes the last node.
+    ///
+    /// This instantiates a template and registers all of its dynamic nodes in a single operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 65535 paths or a path is longer than 255 steps
+    pub fn clone_node_with_ids(&mut self, id: MaybeId, paths: &[(&[u8], NodeId)]) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::CloneNodeWithIds)?;
+            b.encode_maybe_id(id)?;
+            b.encode_paths(paths)
+        })
     }
 
     /// Move the last node to the first child
     pub fn first_child(&mut self) {
-        self.encode_op(Op::FirstChild);
+        self.encode_with(|b| b.try_encode_op(Op::FirstChild))
+    }
+
+    /// Move the last node to the last child
+    pub fn last_child(&mut self) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::FirstChild)?;
+            b.encode_bool(true);
+            Ok(())
+        })
+    }
+
+    /// Move the last node to the child at index `n`. The index counts every child node including text and comment nodes.
+    pub fn nth_child(&mut self, n: u32) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::FirstChild)?;
+            b.encode_bool(false);
+            b.encode_bool(true);
+            b.encode_u32(n)
+        })
     }
 
     /// Move the last node to the next sibling
     pub fn next_sibling(&mut self) {
-        self.encode_op(Op::NextSibling);
+        self.encode_with(|b| b.try_encode_op(Op::NextSibling))
+    }
+
+    /// Move the last node to the previous sibling
+    pub fn previous_sibling(&mut self) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::NextSibling)?;
+            b.encode_bool(true);
+            Ok(())
+        })
+    }
+
+    /// Move the last node along a path of child indices. `navigate(&[3, 1])` moves the last node to the second child of the fourth child of the last node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is longer than 255 steps
+    pub fn navigate(&mut self, path: &[u8]) {
+        assert!(
+            path.len() <= u8::MAX as usize,
+            "paths can be at most 255 steps long"
+        );
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Navigate)?;
+            b.encode_bool(false);
+            b.encode_path(path)
+        })
+    }
+
+    /// Store the nodes at paths of child indices from a node with ids without moving the last node. This registers many descendants of a freshly cloned subtree in a single operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 65535 paths or a path is longer than 255 steps
+    pub fn store_paths(&mut self, root: MaybeId, paths: &[(&[u8], NodeId)]) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Navigate)?;
+            b.encode_bool(true);
+            b.encode_maybe_id(root)?;
+            b.encode_paths(paths)
+        })
+    }
+
+    /// Encodes a list of paths and the ids the nodes at those paths are stored with
+    fn encode_paths(&mut self, paths: &[(&[u8], NodeId)]) -> Result<(), BufferFull> {
+        assert!(
+            paths.len() <= u16::MAX as usize,
+            "at most 65535 paths can be stored at once"
+        );
+        self.encode_u16(paths.len() as u16)?;
+        for (path, id) in paths {
+            assert!(
+                path.len() <= u8::MAX as usize,
+                "paths can be at most 255 steps long"
+            );
+            self.encode_path(path)?;
+            self.encode_id(*id)?;
+        }
+        Ok(())
+    }
+
+    /// Encodes a path of child indices with a u8 length
+    fn encode_path(&mut self, path: &[u8]) -> Result<(), BufferFull> {
+        self.msg.try_reserve(1 + path.len())?;
+        unsafe {
+            self.encode_u8_prealloc(path.len() as u8);
+        }
+        self.msg.try_extend_from_slice(path)
     }
 
     /// Move the last node to the parent node
     pub fn parent_node(&mut self) {
-        self.encode_op(Op::ParentNode);
+        self.encode_with(|b| b.try_encode_op(Op::ParentNode))
+    }
+
+    /// Save the last node on the cursor stack. The last node can be restored with [`Batch::pop_cursor`] after navigating away from it.
+    pub fn push_cursor(&mut self) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Cursor)?;
+            b.encode_bool(false);
+            Ok(())
+        })
+    }
+
+    /// Restore the last node that was saved with [`Batch::push_cursor`] and remove it from the cursor stack.
+    pub fn pop_cursor(&mut self) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Cursor)?;
+            b.encode_bool(true);
+            Ok(())
+        })
     }
 
     /// Store the last node with the given id. This is useful when traversing the document tree.
     pub fn store_with_id(&mut self, id: NodeId) {
-        self.encode_op(Op::StoreWithId);
-        self.encode_id(id);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::StoreWithId)?;
+            b.encode_id(id)
+        })
     }
 
     /// Set the last node to the given id. The last node can be used to traverse the document tree without passing objects between wasm and js every time.
     pub fn set_last_node(&mut self, id: NodeId) {
-        self.encode_op(Op::SetLastNode);
-        self.encode_id(id);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetLastNode)?;
+            b.encode_id(id)
+        })
     }
 
     /// Build a full element, slightly more efficent than creating the element creating the element with `create_element` and then setting the attributes.
     pub fn build_full_element(&mut self, el: ElementBuilder) {
-        self.encode_op(Op::BuildFullElement);
-        el.encode(self);
+        self.encode_with(|b| {
+            b.try_encode_op(Op::BuildFullElement)?;
+            el.encode(b)
+        })
+    }
+
+    /// Build a full element from an [`OwnedElementBuilder`]. This is encoded the same way as [`Batch::build_full_element`].
+    pub fn build_owned_element(&mut self, el: &OwnedElementBuilder) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::BuildFullElement)?;
+            el.encode(b)
+        })
     }
 
     /// Build a text node
@@ -361,26 +820,280 @@ impl Batch {
         self.create_text_node(text.text, text.id)
     }
 
+    /// Set many attributes on a node.
+    pub fn set_attributes(&mut self, attributes: &[(AnyAttribute, &str)], id: MaybeId) {
+        assert!(
+            attributes.len() <= u8::MAX as usize,
+            "at most 255 attributes can be set at once"
+        );
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetAttributes)?;
+            let size = id.encoded_size() as usize
+                + 1
+                + attributes
+                    .iter()
+                    .map(|(attr, _)| attr.size_with_u8_discriminant() + 2)
+                    .sum::<usize>();
+            b.msg.try_reserve(size)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_u8_prealloc(attributes.len() as u8);
+                for (attr, value) in attributes {
+                    attr.encode_u8_discriminant_prealloc(b)?;
+                    b.encode_str_prealloc(*value)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Set an attribute on many nodes with a different value for each node.
+    pub fn set_attribute_many<'a, 'b, A>(&mut self, attr: A, values: &[(NodeId, &str)])
+    where
+        A: IntoAttribue<'a, 'b>,
+    {
+        assert!(
+            values.len() <= u16::MAX as usize,
+            "at most 65535 nodes can be set at once"
+        );
+        let attr: AnyAttribute = attr.into();
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetAttributeMany)?;
+            let size = attr.size_with_u8_discriminant() + 2 + values.len() * (4 + 2);
+            b.msg.try_reserve(size)?;
+            b.encode_bool(false);
+            unsafe {
+                attr.encode_u8_discriminant_prealloc(b)?;
+                b.encode_u16_prealloc(values.len() as u16);
+                for (id, value) in values {
+                    b.encode_id_prealloc(*id);
+                    b.encode_str_prealloc(*value)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Set an attribute on many nodes with the same value for every node.
+    pub fn set_attribute_all<'a, 'b, A>(
+        &mut self,
+        attr: A,
+        value: impl WritableText,
+        ids: &[NodeId],
+    ) where
+        A: IntoAttribue<'a, 'b>,
+    {
+        assert!(
+            ids.len() <= u16::MAX as usize,
+            "at most 65535 nodes can be set at once"
+        );
+        let attr: AnyAttribute = attr.into();
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetAttributeMany)?;
+            let size = attr.size_with_u8_discriminant() + 2 + 2 + ids.len() * 4;
+            b.msg.try_reserve(size)?;
+            b.encode_bool(true);
+            unsafe {
+                attr.encode_u8_discriminant_prealloc(b)?;
+                b.encode_u16_prealloc(ids.len() as u16);
+                b.encode_str_prealloc(value)?;
+                for id in ids {
+                    b.encode_id_prealloc(*id);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Set a property of a node's dataset. The key is the camel case name of the property, so `rowId` sets the `data-row-id` attribute.
+    pub fn set_dataset(&mut self, key: &str, value: impl WritableText, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Dataset)?;
+            b.msg.try_reserve(id.encoded_size() as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+            }
+            b.encode_bool(false);
+            b.encode_cachable_str(key)?;
+            b.encode_str(value)
+        })
+    }
+
+    /// Remove a property from a node's dataset. The key is the camel case name of the property like in [`Batch::set_dataset`].
+    pub fn remove_dataset(&mut self, key: &str, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::Dataset)?;
+            b.msg.try_reserve(id.encoded_size() as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+            }
+            b.encode_bool(true);
+            b.encode_cachable_str(key)
+        })
+    }
+
+    /// Add a boolean attribute like `disabled` or `hidden` to a node if the value is true or remove it if the value is false.
+    pub fn set_bool_attribute<'a, 'b, A>(&mut self, attr: A, value: bool, id: MaybeId)
+    where
+        A: IntoAttribue<'a, 'b>,
+    {
+        self.encode_bool_attribute(attr.into(), Some(value), id)
+    }
+
+    /// Remove a boolean attribute from a node if the node has it or add it if the node does not have it.
+    pub fn toggle_attribute<'a, 'b, A>(&mut self, attr: A, id: MaybeId)
+    where
+        A: IntoAttribue<'a, 'b>,
+    {
+        self.encode_bool_attribute(attr.into(), None, id)
+    }
+
+    /// Encode a boolean attribute that is set to the value, or toggled if there is no value
+    fn encode_bool_attribute(&mut self, attr: AnyAttribute, value: Option<bool>, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::ToggleAttribute)?;
+            let size = id.encoded_size() as usize + attr.size_with_u8_discriminant();
+            b.msg.try_reserve(size)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                attr.encode_u8_discriminant_prealloc(b)?;
+            }
+            b.encode_bool(value.is_none());
+            b.encode_bool(value == Some(true));
+            Ok(())
+        })
+    }
+
     /// Set a style property on a node.
-    pub fn set_style(&mut self, style: &str, value: &str, id: MaybeId) {
-        self.encode_op(Op::SetStyle);
-        let size = id.encoded_size() + 2 + 2;
-        self.msg.reserve(size as usize);
-        self.encode_maybe_id(id);
-        unsafe {
-            self.encode_str_prealloc(style);
-            self.encode_str_prealloc(value);
-        }
+    pub fn set_style<'a, S>(&mut self, style: S, value: impl WritableText, id: MaybeId)
+    where
+        S: IntoStyle<'a>,
+    {
+        self.encode_style(style, value, false, id)
+    }
+
+    /// Set a style property on a node with the `important` priority.
+    pub fn set_style_important<'a, S>(&mut self, style: S, value: impl WritableText, id: MaybeId)
+    where
+        S: IntoStyle<'a>,
+    {
+        self.encode_style(style, value, true, id)
+    }
+
+    fn encode_style<'a, S>(
+        &mut self,
+        style: S,
+        value: impl WritableText,
+        important: bool,
+        id: MaybeId,
+    ) where
+        S: IntoStyle<'a>,
+    {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetStyle)?;
+            let size = S::SINGLE_BYTE as u8 + id.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                style.encode_prealloc(b)?;
+            }
+            b.encode_bool(important);
+            b.encode_str(value)
+        })
+    }
+
+    /// Set a number of style properties on a node in a single operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 255 styles are set at once.
+    pub fn set_styles(&mut self, styles: &[(AnyStyle, &str)], id: MaybeId) {
+        assert!(
+            styles.len() <= u8::MAX as usize,
+            "at most 255 styles can be set at once"
+        );
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetStyles)?;
+            let size = id.encoded_size() as usize
+                + 1
+                + styles
+                    .iter()
+                    .map(|(style, _)| style.size_with_u8_discriminant() + 2)
+                    .sum::<usize>();
+            b.msg.try_reserve(size)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_u8_prealloc(styles.len() as u8);
+                for (style, value) in styles {
+                    style.encode_u8_discriminant_prealloc(b)?;
+                    b.encode_str_prealloc(*value)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Replace all inline styles of a node with the css text.
+    pub fn set_css_text(&mut self, text: impl WritableText, id: MaybeId) {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::SetCssText)?;
+            let size = id.encoded_size() + 2;
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                b.encode_str_prealloc(text)
+            }
+        })
     }
 
     /// Remove a style property from a node.
-    pub fn remove_style(&mut self, style: &str, id: MaybeId) {
-        self.encode_op(Op::RemoveStyle);
-        let size = id.encoded_size() + 2;
-        self.msg.reserve(size as usize);
-        unsafe {
-            self.encode_maybe_id_prealloc(id);
-            self.encode_str_prealloc(style);
+    pub fn remove_style<'a, S>(&mut self, style: S, id: MaybeId)
+    where
+        S: IntoStyle<'a>,
+    {
+        self.encode_with(|b| {
+            b.try_encode_op(Op::RemoveStyle)?;
+            let size = S::SINGLE_BYTE as u8 + id.encoded_size();
+            b.msg.try_reserve(size as usize)?;
+            unsafe {
+                b.encode_maybe_id_prealloc(id);
+                style.encode_prealloc(b)
+            }
+        })
+    }
+
+    /// Encode a single operation. If the operation does not fit in the buffers, everything it wrote is rolled back and the batch is marked as full.
+    #[inline]
+    pub(crate) fn encode_with(&mut self, f: impl FnOnce(&mut Self) -> Result<(), BufferFull>) {
+        if self.full {
+            return;
+        }
+        let msg_len = self.msg.len();
+        let str_len = self.str_buf.len();
+        let op_batch_idx = self.current_op_batch_idx;
+        let op_byte_idx = self.current_op_byte_idx;
+        let bit_pack_index = self.current_op_bit_pack_index;
+        let result = f(self).and_then(|_| {
+            // always leave room for a new op batch so the batch can be finalized with a stop op
+            if self.current_op_byte_idx - self.current_op_batch_idx >= 3 {
+                self.msg.try_reserve(4)
+            } else {
+                Ok(())
+            }
+        });
+        if result.is_err() {
+            unsafe {
+                self.msg.set_len(msg_len);
+                self.str_buf.set_len(str_len);
+            }
+            self.current_op_batch_idx = op_batch_idx;
+            self.current_op_byte_idx = op_byte_idx;
+            self.current_op_bit_pack_index = bit_pack_index;
+            // clear the op byte if the op was written into the existing op batch
+            if op_byte_idx - op_batch_idx < 3 {
+                self.msg.as_mut_slice()[op_byte_idx + 1] = 0;
+            }
+            self.full = true;
         }
     }
 
@@ -411,27 +1124,41 @@ impl Batch {
     }
 
     #[inline]
-    pub(crate) fn encode_maybe_id_u8_discriminant(&mut self, id: MaybeId) {
+    pub(crate) fn encode_maybe_ids_u8_discriminant(
+        &mut self,
+        ids: &[MaybeId],
+    ) -> Result<(), BufferFull> {
+        self.msg.try_push(ids.len() as u8)?;
+        for id in ids {
+            self.encode_maybe_id_u8_discriminant(*id)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn encode_maybe_id_u8_discriminant(
+        &mut self,
+        id: MaybeId,
+    ) -> Result<(), BufferFull> {
         match id {
             MaybeId::Node(id) => {
-                self.msg.push(1);
-                self.encode_id(id);
-            }
-            MaybeId::LastNode => {
-                self.msg.push(0);
+                self.msg.try_push(1)?;
+                self.encode_id(id)
             }
+            MaybeId::LastNode => self.msg.try_push(0),
         }
     }
 
     #[inline]
-    pub(crate) fn encode_maybe_id(&mut self, id: MaybeId) {
+    pub(crate) fn encode_maybe_id(&mut self, id: MaybeId) -> Result<(), BufferFull> {
         match id {
             MaybeId::Node(id) => {
                 self.encode_bool(true);
-                self.encode_id(id);
+                self.encode_id(id)
             }
             MaybeId::LastNode => {
                 self.encode_bool(false);
+                Ok(())
             }
         }
     }
@@ -442,112 +1169,185 @@ impl Batch {
     }
 
     #[inline(always)]
-    pub(crate) fn encode_id(&mut self, id: NodeId) {
-        self.encode_u32(id.0);
+    pub(crate) fn encode_id(&mut self, id: NodeId) -> Result<(), BufferFull> {
+        self.encode_u32(id.0)
     }
 
     #[inline(always)]
-    pub(crate) fn encode_u32(&mut self, val: u32) {
-        self.msg.reserve(4);
+    pub(crate) fn encode_u32(&mut self, val: u32) -> Result<(), BufferFull> {
+        self.msg.try_reserve(4)?;
         unsafe {
             self.encode_u32_prealloc(val);
         }
+        Ok(())
     }
 
     #[inline(always)]
     pub(crate) unsafe fn encode_u32_prealloc(&mut self, val: u32) {
-        let le = val.to_le();
-        unsafe {
-            let len = self.msg.len();
-            self.msg.as_mut_ptr().add(len).cast::<u32>().write(le);
-            self.msg.set_len(len + 4);
-        }
+        self.encode_bytes_prealloc(val.to_le_bytes());
     }
 
     #[inline(always)]
-    pub(crate) fn encode_u16(&mut self, val: u16) {
-        self.msg.reserve(2);
+    pub(crate) fn encode_u16(&mut self, val: u16) -> Result<(), BufferFull> {
+        self.msg.try_reserve(2)?;
         unsafe {
             self.encode_u16_prealloc(val);
         }
+        Ok(())
     }
 
     #[inline(always)]
     pub(crate) unsafe fn encode_u16_prealloc(&mut self, val: u16) {
-        let le = val.to_le();
-        #[allow(clippy::uninit_vec)]
+        self.encode_bytes_prealloc(val.to_le_bytes());
+    }
+
+    /// Copies the bytes into the message byte by byte so the encoding doesn't depend on the alignment or endianness of the host
+    #[inline(always)]
+    pub(crate) unsafe fn encode_bytes_prealloc<const N: usize>(&mut self, bytes: [u8; N]) {
         unsafe {
             let len = self.msg.len();
-            self.msg.as_mut_ptr().add(len).cast::<u16>().write(le);
-            self.msg.set_len(len + 2);
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), self.msg.spare_ptr(), N);
+            self.msg.set_len(len + N);
         }
     }
 
     #[inline(always)]
-    pub(crate) fn encode_u8_prealloc(&mut self, val: u8) {
-        let le = val.to_le();
-        #[allow(clippy::uninit_vec)]
+    pub(crate) unsafe fn encode_u8_prealloc(&mut self, val: u8) {
         unsafe {
             let len = self.msg.len();
-            self.msg.as_mut_ptr().add(len).write(le);
+            self.msg.spare_ptr().write(val);
             self.msg.set_len(len + 1);
         }
     }
 
     #[inline]
-    pub(crate) fn encode_str(&mut self, string: impl WritableText) {
+    pub(crate) fn encode_str(&mut self, string: impl WritableText) -> Result<(), BufferFull> {
+        let prev_len = self.str_buf.len();
+        string.write_as_text(&mut self.str_buf)?;
+        let len = self.finish_str(prev_len)?;
+        self.encode_u16(len)
+    }
+
+    #[inline]
+    pub(crate) unsafe fn encode_str_prealloc(
+        &mut self,
+        string: impl WritableText,
+    ) -> Result<(), BufferFull> {
+        let prev_len = self.str_buf.len();
+        string.write_as_text(&mut self.str_buf)?;
+        let len = self.finish_str(prev_len)?;
+        self.encode_u16_prealloc(len);
+        Ok(())
+    }
+
+    /// Converts the utf-8 text written to the string buffer after `start` to the string encoding of the batch and returns the length of the text in that encoding
+    #[inline]
+    fn finish_str(&mut self, start: usize) -> Result<u16, BufferFull> {
+        let end = self.str_buf.len();
+        match self.string_encoding {
+            StringEncoding::Utf8 => Ok((end - start) as u16),
+            StringEncoding::Utf16 => {
+                // utf-16 takes at most twice as many bytes as utf-8. The code units are written after the utf-8 text and then moved over it
+                self.str_buf.try_reserve((end - start) * 2)?;
+                unsafe {
+                    let out = self.str_buf.spare_ptr();
+                    let text = core::slice::from_raw_parts(out.sub(end - start), end - start);
+                    let mut units = 0;
+                    for unit in encode_utf16_lossy(text) {
+                        let [low, high] = unit.to_le_bytes();
+                        out.add(units * 2).write(low);
+                        out.add(units * 2 + 1).write(high);
+                        units += 1;
+                    }
+                    core::ptr::copy(out, out.sub(end - start), units * 2);
+                    self.str_buf.set_len(start + units * 2);
+                    Ok(units as u16)
+                }
+            }
+        }
+    }
+
+    /// Encode the location of a string in memory instead of copying the string into the string buffer
+    #[inline]
+    pub(crate) unsafe fn encode_static_str_prealloc(&mut self, string: &'static str) {
+        self.encode_u32_prealloc(string.as_ptr() as usize as u32);
+        self.encode_u32_prealloc(string.len() as u32);
+    }
+
+    /// Encode a string that was already written as text
+    #[inline]
+    pub(crate) fn encode_raw_str(&mut self, string: &[u8]) -> Result<(), BufferFull> {
         let prev_len = self.str_buf.len();
-        string.write_as_text(&mut self.str_buf);
-        let len = self.str_buf.len() - prev_len;
-        self.encode_u16(len as u16);
+        self.str_buf.try_extend_from_slice(string)?;
+        let len = self.finish_str(prev_len)?;
+        self.encode_u16(len)
     }
 
+    /// Encode a string that was already written as text
     #[inline]
-    pub(crate) unsafe fn encode_str_prealloc(&mut self, string: impl WritableText) {
+    pub(crate) unsafe fn encode_raw_str_prealloc(
+        &mut self,
+        string: &[u8],
+    ) -> Result<(), BufferFull> {
         let prev_len = self.str_buf.len();
-        string.write_as_text(&mut self.str_buf);
-        let len = self.str_buf.len() - prev_len;
-        self.encode_u16_prealloc(len as u16);
+        self.str_buf.try_extend_from_slice(string)?;
+        let len = self.finish_str(prev_len)?;
+        self.encode_u16_prealloc(len);
+        Ok(())
     }
 
     #[inline]
-    pub(crate) fn encode_cachable_str(&mut self, string: impl WritableText) {
+    pub(crate) fn encode_cachable_str(
+        &mut self,
+        string: impl WritableText,
+    ) -> Result<(), BufferFull> {
         let prev_len = self.str_buf.len();
-        string.write_as_text(&mut self.str_buf);
-        let len = self.str_buf.len() - prev_len;
-        self.encode_u16(len as u16);
+        string.write_as_text(&mut self.str_buf)?;
+        let len = self.finish_str(prev_len)?;
+        self.encode_u16(len)
     }
 
     #[inline]
     #[doc(hidden)]
     pub fn encode_op(&mut self, op: Op) {
-        let u8_op = op as u8;
+        self.encode_with(|b| b.try_encode_op(op))
+    }
 
-        self.current_op_byte_idx += 1;
-        if self.current_op_byte_idx - self.current_op_batch_idx < 4 {
+    #[inline]
+    pub(crate) fn try_encode_op(&mut self, op: Op) -> Result<(), BufferFull> {
+        self.encode_raw_op(op as u8)
+    }
+
+    /// Encode an op that may already have bools packed into it
+    #[inline]
+    pub(crate) fn encode_raw_op(&mut self, u8_op: u8) -> Result<(), BufferFull> {
+        if self.current_op_byte_idx - self.current_op_batch_idx < 3 {
+            self.current_op_byte_idx += 1;
             unsafe {
-                *self.msg.get_unchecked_mut(self.current_op_byte_idx) = u8_op;
+                *self
+                    .msg
+                    .as_mut_slice()
+                    .get_unchecked_mut(self.current_op_byte_idx) = u8_op;
             }
         } else {
-            self.current_op_batch_idx = self.msg.len();
-            self.current_op_byte_idx = self.current_op_batch_idx;
-            // reserve four bytes for the op batch
-            #[allow(clippy::uninit_vec)]
-            unsafe {
-                let len = self.msg.len();
-                self.msg.reserve(4);
-                self.msg.set_len(len + 4);
-                *self.msg.get_unchecked_mut(self.current_op_batch_idx) = u8_op;
-            }
+            // reserve four bytes for the op batch. The unused ops are zeroed so the same batch always encodes to the same bytes
+            let batch_idx = self.msg.len();
+            self.msg.try_extend_from_slice(&[u8_op, 0, 0, 0])?;
+            self.current_op_batch_idx = batch_idx;
+            self.current_op_byte_idx = batch_idx;
         }
         self.current_op_bit_pack_index = 0;
+        Ok(())
     }
 
     pub(crate) fn encode_bool(&mut self, value: bool) {
         if self.current_op_bit_pack_index < 3 {
             if value {
                 unsafe {
-                    *self.msg.get_unchecked_mut(self.current_op_byte_idx) |=
+                    *self
+                        .msg
+                        .as_mut_slice()
+                        .get_unchecked_mut(self.current_op_byte_idx) |=
                         1 << (self.current_op_bit_pack_index + 5);
                 }
             }
@@ -557,17 +1357,851 @@ impl Batch {
         }
     }
 
-    pub fn append(&mut self, mut batch: Self) {
-        // add empty operations to the batch to make sure the batch is aligned
-        let operations_left = 3 - (self.current_op_byte_idx - self.current_op_batch_idx);
-        for _ in 0..operations_left {
-            self.encode_op(Op::NoOp);
+    /// Append the operations of another batch to this batch. If this batch cannot fit every operation in the other batch, none of them are appended and this batch is marked as full.
+    ///
+    /// If the other batch is full, the operations it was able to encode are appended and this batch is marked as full as well, because the operations that did not fit in the other batch are missing.
+    ///
+    /// If the batches encode strings differently (see [`Batch::set_string_encoding`]), the strings of the other batch are converted to the string encoding of this batch first. A utf-16 string can be too long to encode after it is converted to utf-8, in which case nothing is appended and this batch is marked as full.
+    pub fn append(&mut self, batch: Batch<impl Buffer>) {
+        if self.string_encoding != batch.string_encoding {
+            match batch.transcoded(self.string_encoding) {
+                Some(batch) => self.append(batch),
+                None => self.full = true,
+            }
+            return;
+        }
+        self.encode_with(|b| {
+            // add empty operations to the batch to make sure the batch is aligned
+            let operations_left = 3 - (b.current_op_byte_idx - b.current_op_batch_idx);
+            for _ in 0..operations_left {
+                b.try_encode_op(Op::NoOp)?;
+            }
+
+            let msg_len = b.msg.len();
+            b.str_buf.try_extend_from_slice(batch.str_buf.as_slice())?;
+            b.msg.try_extend_from_slice(batch.msg.as_slice())?;
+            b.current_op_byte_idx = msg_len + batch.current_op_byte_idx;
+            b.current_op_batch_idx = msg_len + batch.current_op_batch_idx;
+            b.current_op_bit_pack_index = batch.current_op_bit_pack_index;
+            Ok(())
+        });
+        if batch.full {
+            self.full = true;
+        }
+    }
+
+    /// Copies the batch with its strings converted to another string encoding, or returns `None` if a converted string is too long to encode
+    fn transcoded(&self, encoding: StringEncoding) -> Option<Batch> {
+        let copy = |batch: &Self, str_buf: Vec<u8>| Batch {
+            msg: batch.msg.as_slice().to_vec(),
+            str_buf,
+            current_op_batch_idx: batch.current_op_batch_idx,
+            current_op_byte_idx: batch.current_op_byte_idx,
+            current_op_bit_pack_index: batch.current_op_bit_pack_index,
+            full: batch.full,
+            string_encoding: batch.string_encoding,
+        };
+        // the operations must be finalized to find the strings
+        let finalized = copy(self, self.str_buf.as_slice().to_vec()).finalize();
+        let lengths = string_lengths(&finalized.msg, &finalized.str, self.string_encoding);
+
+        let mut transcoded = copy(self, Vec::new());
+        transcoded.string_encoding = encoding;
+        let mut str_pos = 0;
+        for pos in lengths {
+            let len = u16::from_le_bytes([finalized.msg[pos], finalized.msg[pos + 1]]) as usize;
+            let start = transcoded.str_buf.len();
+            let len = match self.string_encoding {
+                StringEncoding::Utf8 => {
+                    let text = &finalized.str[str_pos..str_pos + len];
+                    str_pos += len;
+                    for unit in encode_utf16_lossy(text) {
+                        transcoded.str_buf.extend_from_slice(&unit.to_le_bytes());
+                    }
+                    (transcoded.str_buf.len() - start) / 2
+                }
+                StringEncoding::Utf16 => {
+                    let units = &finalized.str[str_pos..str_pos + len * 2];
+                    str_pos += len * 2;
+                    let units = units
+                        .chunks_exact(2)
+                        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+                    for c in char::decode_utf16(units) {
+                        c.unwrap_or(char::REPLACEMENT_CHARACTER)
+                            .write_as_text(&mut transcoded.str_buf)
+                            .ok()?;
+                    }
+                    transcoded.str_buf.len() - start
+                }
+            };
+            let len = u16::try_from(len).ok()?;
+            transcoded.msg[pos..pos + 2].copy_from_slice(&len.to_le_bytes());
+        }
+        Some(transcoded)
+    }
+}
+
+/// Encodes utf-8 text as utf-16 code units, replacing invalid utf-8 with U+FFFD
+fn encode_utf16_lossy(text: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    text.utf8_chunks().flat_map(|chunk| {
+        let replacement = (!chunk.invalid().is_empty()).then_some(0xFFFD);
+        chunk.valid().encode_utf16().chain(replacement)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::SliceBuffer,
+        extended::{MathMlAttribute, MathMlElement, SvgAttribute, SvgElement, EXTENDED},
+        namespace::{KnownNamespace, Namespace, STR_NAMESPACE},
+        prefixed::{aria, data, ARIA_PREFIX, DATA_PREFIX},
+        style::STR_STYLE,
+        Attribute, Element, Style, WithNsExt,
+    };
+    use alloc::string::ToString;
+
+    #[test]
+    fn encoding_is_portable() {
+        let mut batch = Batch::default();
+        batch.create_element(Element::div, Some(NodeId(1)));
+        batch.set_attribute(Attribute::class, "a", MaybeId::LastNode);
+        batch.create_text_node("hi", None);
+        batch.append_child(MaybeId::Node(NodeId(1)), MaybeId::LastNode);
+        batch.set_style(Style::color, "red", MaybeId::Node(NodeId(0x01020304)));
+        batch.remove(MaybeId::Node(NodeId(1)));
+        let batch = batch.finalize();
+
+        // the layout must not change between hosts or versions of the encoder
+        #[rustfmt::skip]
+        let msg = [
+            // create element with an id, set attribute, create text node, append child to an id
+            45, 15, 12, 39,
+            // div, NodeId(1)
+            36, 1, 0, 0, 0,
+            // class, 1 byte
+            43, 1, 0,
+            // 2 bytes
+            2, 0,
+            // NodeId(1)
+            1, 0, 0, 0,
+            // set style on an id, remove an id, stop
+            49, 43, 5, 0,
+            // NodeId(0x01020304), color, 3 bytes
+            4, 3, 2, 1, 30, 3, 0,
+            // NodeId(1)
+            1, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"ahired");
+    }
+
+    #[test]
+    fn rolls_back_operations_that_do_not_fit() {
+        let mut msg = [0; 16];
+        let mut str = [0; 6];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.set_text("abcd", MaybeId::Node(NodeId(1)));
+        assert!(!batch.is_full());
+        let (msg_len, str_len) = (batch.msg.len(), batch.str_buf.len());
+
+        // the text does not fit in the string buffer
+        batch.set_text("efgh", MaybeId::Node(NodeId(2)));
+        assert!(batch.is_full());
+        assert_eq!(batch.msg.len(), msg_len);
+        assert_eq!(batch.str_buf.len(), str_len);
+
+        // operations after the batch is full are not encoded even if they fit
+        batch.set_last_node(NodeId(1));
+        assert_eq!(batch.msg.len(), msg_len);
+
+        let mut expected = Batch::default();
+        expected.set_text("abcd", MaybeId::Node(NodeId(1)));
+        let expected = expected.finalize();
+        let batch = batch.finalize();
+        assert_eq!(batch.msg.as_slice(), expected.msg);
+        assert_eq!(batch.str.as_slice(), expected.str);
+    }
+
+    #[test]
+    fn appending_a_full_batch_marks_the_batch_full() {
+        let mut msg = [0; 16];
+        let mut str = [0; 4];
+        let mut full = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        full.set_text("ab", MaybeId::Node(NodeId(1)));
+        full.set_text("cdef", MaybeId::Node(NodeId(2)));
+        assert!(full.is_full());
+
+        let mut batch = Batch::default();
+        batch.append(full);
+        assert!(batch.is_full());
+
+        let mut expected = Batch::default();
+        expected.set_text("ab", MaybeId::Node(NodeId(1)));
+        assert_eq!(batch.finalize().msg, expected.finalize().msg);
+    }
+
+    /// Encodes operations with strings in the message, the element and the attribute
+    fn encode_strings(batch: &mut Batch) {
+        batch.create_element("my-élément", Some(NodeId(1)));
+        batch.set_attribute("data-🦀", "a€b", MaybeId::LastNode);
+        batch.set_text("", MaybeId::LastNode);
+        batch.set_text("héllo 🦀", MaybeId::Node(NodeId(1)));
+    }
+
+    fn append_with_encodings(from: StringEncoding, to: StringEncoding) {
+        let mut other = Batch::default();
+        other.set_string_encoding(from);
+        encode_strings(&mut other);
+
+        let mut batch = Batch::default();
+        batch.set_string_encoding(to);
+        batch.set_text("first", MaybeId::LastNode);
+        batch.append(other);
+        assert!(!batch.is_full());
+
+        // the same batch encoded with the target encoding from the start
+        let mut same = Batch::default();
+        same.set_string_encoding(to);
+        encode_strings(&mut same);
+        let mut expected = Batch::default();
+        expected.set_string_encoding(to);
+        expected.set_text("first", MaybeId::LastNode);
+        expected.append(same);
+
+        let batch = batch.finalize();
+        let expected = expected.finalize();
+        assert_eq!(batch.msg, expected.msg);
+        assert_eq!(batch.str, expected.str);
+        assert_eq!(batch.string_encoding, to);
+    }
+
+    #[test]
+    fn appending_utf8_strings_to_a_utf16_batch_converts_them() {
+        append_with_encodings(StringEncoding::Utf8, StringEncoding::Utf16);
+    }
+
+    #[test]
+    fn appending_utf16_strings_to_a_utf8_batch_converts_them() {
+        append_with_encodings(StringEncoding::Utf16, StringEncoding::Utf8);
+    }
+
+    #[test]
+    fn appending_strings_that_are_too_long_after_converting_marks_the_batch_full() {
+        let long = "€".repeat(30_000);
+        let mut other = Batch::default();
+        other.set_string_encoding(StringEncoding::Utf16);
+        other.set_text(long.as_str(), MaybeId::LastNode);
+
+        let mut batch = Batch::default();
+        batch.set_text("first", MaybeId::LastNode);
+        batch.append(other);
+        assert!(batch.is_full());
+
+        let mut expected = Batch::default();
+        expected.set_text("first", MaybeId::LastNode);
+        assert_eq!(batch.finalize().msg, expected.finalize().msg);
+    }
+
+    /// Writes "a", a byte that is never valid utf-8 and "b"
+    fn invalid_utf8(buf: &mut dyn Buffer) -> Result<(), BufferFull> {
+        buf.try_extend_from_slice(&[b'a', 0xff, b'b'])
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced_when_encoding_utf16() {
+        let mut batch = Batch::default();
+        batch.set_string_encoding(StringEncoding::Utf16);
+        batch.set_text(invalid_utf8, MaybeId::LastNode);
+        let batch = batch.finalize();
+        assert_eq!(batch.msg[4..6], 3u16.to_le_bytes());
+        assert_eq!(batch.str, [b'a', 0, 0xfd, 0xff, b'b', 0]);
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced_when_appending_to_a_utf16_batch() {
+        let mut other = Batch::default();
+        other.set_text(invalid_utf8, MaybeId::LastNode);
+
+        let mut batch = Batch::default();
+        batch.set_string_encoding(StringEncoding::Utf16);
+        batch.append(other);
+
+        let mut expected = Batch::default();
+        expected.set_string_encoding(StringEncoding::Utf16);
+        expected.set_text("a\u{fffd}b", MaybeId::LastNode);
+        let batch = batch.finalize();
+        let expected = expected.finalize();
+        assert_eq!(batch.msg, expected.msg);
+        assert_eq!(batch.str, expected.str);
+    }
+
+    #[test]
+    fn edit_text_layout() {
+        let mut batch = Batch::default();
+        batch.append_text("ab", MaybeId::Node(NodeId(1)));
+        batch.insert_text(2, "c", MaybeId::LastNode);
+        batch.delete_text(1, 2, MaybeId::Node(NodeId(0x01020304)));
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // append text to an id, insert text, delete text from an id, stop
+            59, 91, 187, 5,
+            // NodeId(1), 2 bytes
+            1, 0, 0, 0, 2, 0,
+            // offset 2, 1 byte
+            2, 0, 0, 0, 1, 0,
+            // NodeId(0x01020304), offset 1, count 2
+            4, 3, 2, 1, 1, 0, 0, 0, 2, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"abc");
+    }
+
+    #[test]
+    fn text_edits_that_do_not_fit_are_rolled_back() {
+        let mut msg = [0; 16];
+        let mut str = [0; 4];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.append_text("abc", MaybeId::LastNode);
+        // the inserted text does not fit in the string buffer
+        batch.insert_text(0, "de", MaybeId::Node(NodeId(1)));
+        assert!(batch.is_full());
+        // deleting text needs no string, but nothing is encoded once the batch is full
+        batch.delete_text(0, 1, MaybeId::LastNode);
+
+        let batch = batch.finalize();
+        assert_eq!(batch.msg.as_slice(), [27, 5, 0, 0, 3, 0]);
+        assert_eq!(batch.str.as_slice(), b"abc");
+    }
+
+    #[test]
+    fn node_stack_layout() {
+        let mut batch = Batch::default();
+        batch.push_root(MaybeId::Node(NodeId(1)));
+        batch.push_root(MaybeId::LastNode);
+        batch.append_children(MaybeId::Node(NodeId(2)), 2);
+        batch.append_children(MaybeId::LastNode, 1);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // push root with an id, push root, append children from the stack to an id, append children from the stack
+            60, 28, 167, 135,
+            // NodeId(1)
+            1, 0, 0, 0,
+            // NodeId(2), 2 nodes
+            2, 0, 0, 0, 2, 0,
+            // 1 node
+            1, 0,
+            // stop
+            5, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert!(batch.str.is_empty());
+    }
+
+    #[test]
+    fn stack_inserts_only_differ_in_the_op() {
+        type Insert = fn(&mut Batch, MaybeId, u16);
+        let inserts: [(Insert, Op); 3] = [
+            (Batch::replace_with_stack, Op::ReplaceWith),
+            (Batch::insert_stack_after, Op::InsertAfter),
+            (Batch::insert_stack_before, Op::InsertBefore),
+        ];
+        for (insert, op) in inserts {
+            let mut batch = Batch::default();
+            insert(&mut batch, MaybeId::Node(NodeId(3)), 0x0201);
+            let batch = batch.finalize();
+            // the many nodes bool, the root id bool and the stack bool are set
+            assert_eq!(batch.msg[0], op as u8 | 0x20 | 0x40 | 0x80);
+            assert_eq!(batch.msg[4..], [3, 0, 0, 0, 1, 2]);
         }
+    }
+
+    #[test]
+    fn navigation_layout() {
+        let mut batch = Batch::default();
+        batch.last_child();
+        batch.nth_child(300);
+        batch.previous_sibling();
+        batch.navigate(&[3, 1]);
+        batch.store_paths(
+            MaybeId::Node(NodeId(7)),
+            &[(&[], NodeId(8)), (&[0, 2], NodeId(9))],
+        );
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // last child, nth child, previous sibling, navigate
+            32, 64, 33, 29,
+            // child 300
+            44, 1, 0, 0,
+            // a path of 2 steps
+            2, 3, 1,
+            // store paths from an id, stop
+            125, 5, 0, 0,
+            // NodeId(7), 2 paths
+            7, 0, 0, 0, 2, 0,
+            // an empty path stored as NodeId(8)
+            0, 8, 0, 0, 0,
+            // a path of 2 steps stored as NodeId(9)
+            2, 0, 2, 9, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert!(batch.str.is_empty());
+    }
+
+    #[test]
+    fn paths_that_do_not_fit_are_rolled_back() {
+        let mut msg = [0; 16];
+        let mut str = [0; 0];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.navigate(&[1]);
+        // the first path fits, the second one does not
+        batch.store_paths(
+            MaybeId::LastNode,
+            &[(&[0], NodeId(1)), (&[1, 2], NodeId(2))],
+        );
+        assert!(batch.is_full());
+        assert_eq!(batch.finalize().msg.as_slice(), [29, 5, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "paths can be at most 255 steps long")]
+    fn navigating_a_path_longer_than_255_steps_panics() {
+        Batch::default().navigate(&[0; 256]);
+    }
+
+    #[test]
+    fn cursor_ops_only_take_an_op_byte() {
+        let mut msg = [0; 4];
+        let mut str = [0; 0];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.push_cursor();
+        batch.first_child();
+        batch.pop_cursor();
+        assert!(!batch.is_full());
+        assert_eq!(batch.msg.as_slice(), [30, 0, 62, 0]);
+
+        // a fourth op would leave no room for the stop, so it is rolled back
+        batch.push_cursor();
+        assert!(batch.is_full());
+        assert_eq!(batch.finalize().msg.as_slice(), [30, 0, 62, 5]);
+    }
+
+    #[test]
+    fn clone_node_with_ids_layout() {
+        let mut batch = Batch::default();
+        batch.clone_node_with_ids(
+            MaybeId::Node(NodeId(1)),
+            &[(&[], NodeId(2)), (&[1], NodeId(3))],
+        );
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // clone node with ids from an id, stop
+            63, 5, 0, 0,
+            // NodeId(1), 2 paths
+            1, 0, 0, 0, 2, 0,
+            // an empty path stored as NodeId(2)
+            0, 2, 0, 0, 0,
+            // a path of 1 step stored as NodeId(3)
+            1, 1, 3, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+    }
+
+    #[test]
+    fn shallow_clones_only_set_the_shallow_bool() {
+        let mut deep = Batch::default();
+        deep.clone_node(MaybeId::LastNode, MaybeId::Node(NodeId(4)));
+        let mut shallow = Batch::default();
+        shallow.clone_node_shallow(MaybeId::LastNode, MaybeId::Node(NodeId(4)));
+        let (deep, shallow) = (deep.finalize(), shallow.finalize());
+
+        assert_eq!(deep.msg[0], Op::CloneNode as u8 | 0x40);
+        assert_eq!(shallow.msg[0], deep.msg[0] | 0x80);
+        assert_eq!(shallow.msg[1..], deep.msg[1..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 65535 paths can be stored at once")]
+    fn cloning_with_more_than_65535_paths_panics() {
+        let paths = alloc::vec![(&[][..], NodeId(0)); u16::MAX as usize + 1];
+        Batch::default().clone_node_with_ids(MaybeId::LastNode, &paths);
+    }
+
+    #[test]
+    fn namespace_layout() {
+        let mut names = crate::custom::CustomNames::new();
+        let custom = names.namespace("urn:custom");
+        let mut batch = Batch::default();
+        batch.create_element(Element::div.in_namespace(KnownNamespace::svg), None);
+        batch.create_element(Element::div.in_namespace(custom), None);
+        batch.create_element(Element::div.in_namespace("urn:other"), None);
+        batch.set_attribute(
+            Attribute::href.in_namespace(KnownNamespace::xlink),
+            "a",
+            MaybeId::LastNode,
+        );
+        // lists of attributes mark a namespaced attribute with a 255 byte instead of a bool
+        batch.set_attributes(
+            &[(Attribute::href.in_namespace("urn:other").into(), "b")],
+            MaybeId::LastNode,
+        );
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // three elements and an attribute with the namespace bool
+            13, 13, 13, 15 | 0x80,
+            // element, the svg namespace
+            255, Element::div as u8, KnownNamespace::svg as u8,
+            // element, the custom namespace
+            255, Element::div as u8, custom.code(),
+            // element, namespace as a string of 9 bytes
+            255, Element::div as u8, STR_NAMESPACE, 9, 0,
+            // attribute, the xlink namespace, 1 byte
+            Attribute::href as u8, KnownNamespace::xlink as u8, 1, 0,
+            // set attributes, stop
+            24, 5, 0, 0,
+            // one attribute in a namespace, namespace as a string of 9 bytes, 1 byte
+            1, 255, Attribute::href as u8, STR_NAMESPACE, 9, 0, 1, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"urn:otheraurn:otherb");
+    }
+
+    #[test]
+    fn well_known_namespace_uris_are_known() {
+        assert_eq!(
+            Namespace::from("http://www.w3.org/2000/svg"),
+            Namespace::Known(KnownNamespace::svg)
+        );
+        assert_eq!(
+            Namespace::from("http://www.w3.org/1998/Math/MathML"),
+            Namespace::Known(KnownNamespace::mathml)
+        );
+        assert_eq!(Namespace::from("urn:other"), Namespace::Str("urn:other"));
+    }
+
+    #[test]
+    fn extended_names_layout() {
+        let mut batch = Batch::default();
+        batch.create_element(SvgElement::circle, Some(NodeId(1)));
+        batch.create_element(MathMlElement::math, None);
+        batch.set_attribute(SvgAttribute::accumulate, "sum", MaybeId::LastNode);
+        batch.set_attribute(MathMlAttribute::accent, "true", MaybeId::Node(NodeId(2)));
+        let batch = batch.finalize();
+
+        // mathml names come after the svg names in the same table
+        let math = SvgElement::ALL.len() as u8;
+        let accent = SvgAttribute::ALL.len() as u8;
+        #[rustfmt::skip]
+        let msg = [
+            // create element with an id, create element, set attribute, set attribute on an id
+            13 | 0x20, 13, 15, 15 | 0x20,
+            // circle without a namespace byte, NodeId(1)
+            EXTENDED, SvgElement::circle as u8, 1, 0, 0, 0,
+            // math without a namespace byte
+            EXTENDED, math,
+            // accumulate, 3 bytes
+            EXTENDED, SvgAttribute::accumulate as u8, 3, 0,
+            // NodeId(2), accent, 4 bytes
+            2, 0, 0, 0, EXTENDED, accent, 4, 0,
+            // stop
+            5, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"sumtrue");
+    }
+
+    #[test]
+    fn extended_attributes_in_lists_layout() {
+        let mut batch = Batch::default();
+        batch.set_attributes(
+            &[
+                (SvgAttribute::accumulate.into(), "sum"),
+                (MathMlAttribute::accent.into(), "true"),
+            ],
+            MaybeId::LastNode,
+        );
+        let batch = batch.finalize();
+
+        let accent = SvgAttribute::ALL.len() as u8;
+        #[rustfmt::skip]
+        let msg = [
+            // set attributes, stop
+            24, 5, 0, 0,
+            // two attributes, accumulate with 3 bytes, accent with 4 bytes
+            2, EXTENDED, SvgAttribute::accumulate as u8, 3, 0, EXTENDED, accent, 4, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"sumtrue");
+    }
+
+    #[test]
+    fn style_layout() {
+        let mut batch = Batch::default();
+        batch.set_style(Style::color, "red", MaybeId::LastNode);
+        batch.set_style("--x", "1", MaybeId::LastNode);
+        batch.remove_style(Style::color, MaybeId::Node(NodeId(1)));
+        batch.set_styles(&[("--x".into(), "2")], MaybeId::LastNode);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // set style, set a style that is a string, remove style from an id, set styles
+            17, 17 | 0x40, 18 | 0x20, 21,
+            // color, 3 bytes
+            Style::color as u8, 3, 0,
+            // --x, 1 byte
+            3, 0, 1, 0,
+            // NodeId(1), color
+            1, 0, 0, 0, Style::color as u8,
+            // one style, lists mark a string with a 255 byte instead of a bool, 3 bytes, 1 byte
+            1, STR_STYLE, 3, 0, 1, 0,
+            // stop
+            5, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"red--x1--x2");
+    }
+
+    #[test]
+    fn style_lists_and_priority_layout() {
+        let mut batch = Batch::default();
+        batch.set_style_important(Style::color, "red", MaybeId::Node(NodeId(1)));
+        batch.set_style_important("--x", "1", MaybeId::LastNode);
+        batch.set_styles(
+            &[
+                (Style::color.into(), "blue"),
+                (Style::display.into(), "none"),
+            ],
+            MaybeId::Node(NodeId(2)),
+        );
+        batch.set_css_text("color: red", MaybeId::LastNode);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // important style on an id, important style that is a string, set styles on an id, set css text
+            17 | 0x20 | 0x80, 17 | 0x40 | 0x80, 21 | 0x20, 22,
+            // NodeId(1), color, 3 bytes
+            1, 0, 0, 0, Style::color as u8, 3, 0,
+            // --x, 1 byte
+            3, 0, 1, 0,
+            // NodeId(2), two styles, color with 4 bytes, display with 4 bytes
+            2, 0, 0, 0, 2, Style::color as u8, 4, 0, Style::display as u8, 4, 0,
+            // 10 bytes
+            10, 0,
+            // stop
+            5, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"red--x1bluenonecolor: red");
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 255 styles")]
+    fn setting_more_than_255_styles_panics() {
+        let styles = [(Style::color.into(), "red"); 256];
+        Batch::default().set_styles(&styles, MaybeId::LastNode);
+    }
+
+    #[test]
+    fn bool_attribute_layout() {
+        let mut batch = Batch::default();
+        batch.set_bool_attribute(Attribute::hidden, true, MaybeId::Node(NodeId(1)));
+        batch.set_bool_attribute(Attribute::hidden, false, MaybeId::LastNode);
+        batch.toggle_attribute(Attribute::disabled, MaybeId::LastNode);
+        batch.toggle_attribute("x-flag", MaybeId::Node(NodeId(2)));
+        let batch = batch.finalize();
+
+        // the bools are the id, if the attribute is toggled and the value
+        #[rustfmt::skip]
+        let msg = [
+            // set to true on an id, set to false, toggle, toggle on an id
+            23 | 0x20 | 0x80, 23, 23 | 0x40, 23 | 0x20 | 0x40,
+            // NodeId(1), hidden
+            1, 0, 0, 0, Attribute::hidden as u8,
+            // hidden
+            Attribute::hidden as u8,
+            // disabled
+            Attribute::disabled as u8,
+            // NodeId(2), x-flag as a string of 6 bytes
+            2, 0, 0, 0, 254, 6, 0,
+            // stop
+            5, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"x-flag");
+    }
+
+    #[test]
+    fn attribute_list_layout() {
+        let mut names = crate::custom::CustomNames::new();
+        let custom = names.attribute("x-custom");
+        let mut batch = Batch::default();
+        batch.set_attributes(
+            &[
+                (Attribute::class.into(), "a"),
+                ("x-y".into(), "b"),
+                (custom.into(), "c"),
+            ],
+            MaybeId::Node(NodeId(1)),
+        );
+        batch.set_attributes(&[], MaybeId::LastNode);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // set attributes on an id, set attributes, stop
+            24 | 0x20, 24, 5, 0,
+            // NodeId(1), three attributes
+            1, 0, 0, 0, 3,
+            // class, 1 byte
+            Attribute::class as u8, 1, 0,
+            // x-y as a string of 3 bytes, 1 byte
+            254, 3, 0, 1, 0,
+            // the custom attribute, 1 byte
+            custom.code(), 1, 0,
+            // no attributes
+            0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"ax-ybc");
+    }
+
+    #[test]
+    fn attribute_lists_that_do_not_fit_are_rolled_back() {
+        let mut msg = [0; 32];
+        let mut str = [0; 4];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.set_text("ab", MaybeId::LastNode);
+        // the first value fits in the string buffer, but the second does not
+        batch.set_attributes(
+            &[
+                (Attribute::class.into(), "c"),
+                (Attribute::title.into(), "de"),
+            ],
+            MaybeId::Node(NodeId(1)),
+        );
+        assert!(batch.is_full());
+
+        let batch = batch.finalize();
+        assert_eq!(batch.msg.as_slice(), [14, 5, 0, 0, 2, 0]);
+        assert_eq!(batch.str.as_slice(), b"ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 255 attributes")]
+    fn setting_more_than_255_attributes_panics() {
+        let attributes = [(Attribute::class.into(), "a"); 256];
+        Batch::default().set_attributes(&attributes, MaybeId::LastNode);
+    }
+
+    #[test]
+    fn attribute_on_many_nodes_layout() {
+        let mut batch = Batch::default();
+        batch.set_attribute_many(Attribute::class, &[(NodeId(1), "a"), (NodeId(2), "bc")]);
+        batch.set_attribute_all("x-y", "v", &[NodeId(3), NodeId(4)]);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // a value for each node, the same value for every node, stop
+            25, 25 | 0x20, 5, 0,
+            // class, two nodes, NodeId(1) with 1 byte, NodeId(2) with 2 bytes
+            Attribute::class as u8, 2, 0, 1, 0, 0, 0, 1, 0, 2, 0, 0, 0, 2, 0,
+            // x-y as a string of 3 bytes, two nodes, 1 byte, NodeId(3), NodeId(4)
+            254, 3, 0, 2, 0, 1, 0, 3, 0, 0, 0, 4, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"abcx-yv");
+    }
+
+    #[test]
+    fn prefixed_attribute_layout() {
+        let mut batch = Batch::default();
+        batch.set_attribute(data("row"), "1", MaybeId::LastNode);
+        batch.set_attribute(aria("x"), "2", MaybeId::Node(NodeId(1)));
+        batch.set_dataset("rowId", "3", MaybeId::LastNode);
+        batch.remove_dataset("rowId", MaybeId::Node(NodeId(2)));
+        batch.set_attributes(&[(aria("y").into(), "4")], MaybeId::LastNode);
+        let batch = batch.finalize();
+
+        #[rustfmt::skip]
+        let msg = [
+            // set attribute, set attribute on an id, set dataset, remove dataset from an id
+            15, 15 | 0x20, 26, 26 | 0x20 | 0x40,
+            // data- with a suffix of 3 bytes, 1 byte
+            DATA_PREFIX, 3, 0, 1, 0,
+            // NodeId(1), aria- with a suffix of 1 byte, 1 byte
+            1, 0, 0, 0, ARIA_PREFIX, 1, 0, 1, 0,
+            // key of 5 bytes, 1 byte
+            5, 0, 1, 0,
+            // NodeId(2), key of 5 bytes
+            2, 0, 0, 0, 5, 0,
+            // set attributes, stop
+            24, 5, 0, 0,
+            // one attribute, aria- with a suffix of 1 byte, 1 byte
+            1, ARIA_PREFIX, 1, 0, 1, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert_eq!(batch.str, b"row1x2rowId3rowIdy4");
+    }
+
+    #[test]
+    fn prefixed_attributes_display_the_full_name() {
+        assert_eq!(data("row-id").to_string(), "data-row-id");
+        assert_eq!(aria("label").to_string(), "aria-label");
+    }
+
+    #[test]
+    fn static_text_layout() {
+        static TEXT: &str = "static text";
+        let mut batch = Batch::default();
+        batch.create_text_node_in_memory(TEXT, Some(NodeId(1)));
+        batch.set_text_in_memory(TEXT, MaybeId::LastNode);
+        batch.set_text_in_memory(TEXT, MaybeId::Node(NodeId(2)));
+        let batch = batch.finalize();
+
+        // wasm pointers are 32 bits
+        let [p0, p1, p2, p3] = (TEXT.as_ptr() as usize as u32).to_le_bytes();
+        #[rustfmt::skip]
+        let msg = [
+            // create a text node with an id, set text, set text on an id, the second bool marks static text
+            12 | 0x20 | 0x40, 14 | 0x40, 14 | 0x20 | 0x40, 5,
+            // pointer, 11 bytes, NodeId(1)
+            p0, p1, p2, p3, 11, 0, 0, 0, 1, 0, 0, 0,
+            // pointer, 11 bytes
+            p0, p1, p2, p3, 11, 0, 0, 0,
+            // NodeId(2), pointer, 11 bytes
+            2, 0, 0, 0, p0, p1, p2, p3, 11, 0, 0, 0,
+        ];
+        assert_eq!(batch.msg, msg);
+        assert!(batch.str.is_empty());
+    }
 
-        self.current_op_byte_idx = self.msg.len() + batch.current_op_byte_idx;
-        self.current_op_batch_idx = self.msg.len() + batch.current_op_batch_idx;
-        self.current_op_bit_pack_index = batch.current_op_bit_pack_index;
-        self.str_buf.extend_from_slice(&batch.str_buf);
-        self.msg.append(&mut batch.msg);
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn static_text_is_copied_outside_of_wasm() {
+        let mut batch = Batch::default();
+        batch.create_static_text_node("a", Some(NodeId(1)));
+        batch.set_static_text("b", MaybeId::LastNode);
+
+        let mut expected = Batch::default();
+        expected.create_text_node("a", Some(NodeId(1)));
+        expected.set_text("b", MaybeId::LastNode);
+        let batch = batch.finalize();
+        let expected = expected.finalize();
+        assert_eq!(batch.msg, expected.msg);
+        assert_eq!(batch.str, expected.str);
     }
 }
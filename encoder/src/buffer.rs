@@ -0,0 +1,242 @@
+//! Byte buffers that a [`Batch`](crate::batch::Batch) can be encoded into.
+//!
+//! By default batches are encoded into a [`Vec<u8>`], but any type that implements [`Buffer`] can be used. This allows encoding directly into a fixed region of memory with [`SliceBuffer`], or into a [`bytes::BytesMut`] with the `bytes` feature.
+
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// The error returned when a buffer cannot grow to fit more data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferFull;
+
+impl Display for BufferFull {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the buffer is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferFull {}
+
+/// A byte buffer that a batch can be encoded into
+///
+/// # Safety
+///
+/// After [`Buffer::try_reserve`] returns `Ok(())` for `additional` bytes, the pointer returned by [`Buffer::spare_ptr`] must be valid for writes of `additional` bytes and [`Buffer::set_len`] must accept any length up to `len + additional`.
+pub unsafe trait Buffer {
+    /// The number of bytes in the buffer
+    fn len(&self) -> usize;
+
+    /// If the buffer is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Make room for at least `additional` more bytes, or return [`BufferFull`] if the buffer cannot grow
+    fn try_reserve(&mut self, additional: usize) -> Result<(), BufferFull>;
+
+    /// The bytes in the buffer
+    fn as_slice(&self) -> &[u8];
+
+    /// The bytes in the buffer as a mutable slice
+    fn as_mut_slice(&mut self) -> &mut [u8];
+
+    /// A pointer to the end of the bytes in the buffer where reserved bytes can be written
+    fn spare_ptr(&mut self) -> *mut u8;
+
+    /// Set the number of bytes in the buffer
+    ///
+    /// # Safety
+    ///
+    /// The bytes up to `len` must be initialized and `len` must not be larger than the space reserved with [`Buffer::try_reserve`]
+    unsafe fn set_len(&mut self, len: usize);
+
+    /// Remove all of the bytes from the buffer
+    fn clear(&mut self) {
+        unsafe { self.set_len(0) }
+    }
+
+    /// Push a single byte to the end of the buffer
+    #[inline(always)]
+    fn try_push(&mut self, byte: u8) -> Result<(), BufferFull> {
+        self.try_extend_from_slice(&[byte])
+    }
+
+    /// Copy bytes to the end of the buffer
+    #[inline(always)]
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), BufferFull> {
+        self.try_reserve(bytes.len())?;
+        unsafe {
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), self.spare_ptr(), bytes.len());
+            self.set_len(self.len() + bytes.len());
+        }
+        Ok(())
+    }
+}
+
+unsafe impl Buffer for Vec<u8> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline(always)]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), BufferFull> {
+        // a vec can always grow, running out of memory aborts like any other allocation
+        self.reserve(additional);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+
+    #[inline(always)]
+    fn spare_ptr(&mut self) -> *mut u8 {
+        unsafe { Vec::as_mut_ptr(self).add(Vec::len(self)) }
+    }
+
+    #[inline(always)]
+    unsafe fn set_len(&mut self, len: usize) {
+        Vec::set_len(self, len)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    #[inline(always)]
+    fn try_push(&mut self, byte: u8) -> Result<(), BufferFull> {
+        self.push(byte);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), BufferFull> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A fixed capacity buffer backed by a mutable slice. This can be used to encode a batch into a preallocated region of memory like a ring buffer or a network frame.
+///
+/// Example:
+/// ```rust
+/// use sledgehammer_encoder::{batch::Batch, buffer::SliceBuffer, NodeId};
+///
+/// let mut msg = [0; 64];
+/// let mut str = [0; 64];
+/// let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+/// batch.create_text_node("Hello from sledgehammer!", Some(NodeId(0)));
+/// assert!(!batch.is_full());
+/// let finalized = batch.finalize();
+/// ```
+pub struct SliceBuffer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceBuffer<'a> {
+    /// Create a new empty buffer that writes into the slice
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// The maximum number of bytes the buffer can hold
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Turn the buffer into the bytes written to it
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.len]
+    }
+}
+
+unsafe impl Buffer for SliceBuffer<'_> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), BufferFull> {
+        if self.buf.len() - self.len >= additional {
+            Ok(())
+        } else {
+            Err(BufferFull)
+        }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+
+    #[inline(always)]
+    fn spare_ptr(&mut self) -> *mut u8 {
+        self.buf[self.len..].as_mut_ptr()
+    }
+
+    #[inline(always)]
+    unsafe fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl Buffer for bytes::BytesMut {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        bytes::BytesMut::len(self)
+    }
+
+    #[inline(always)]
+    fn try_reserve(&mut self, additional: usize) -> Result<(), BufferFull> {
+        self.reserve(additional);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    #[inline(always)]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+
+    #[inline(always)]
+    fn spare_ptr(&mut self) -> *mut u8 {
+        bytes::BufMut::chunk_mut(self).as_mut_ptr()
+    }
+
+    #[inline(always)]
+    unsafe fn set_len(&mut self, len: usize) {
+        bytes::BytesMut::set_len(self, len)
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        bytes::BytesMut::clear(self)
+    }
+
+    #[inline(always)]
+    fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), BufferFull> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
@@ -0,0 +1,528 @@
+//! Typed css values that are written directly into the string buffer of a batch.
+//!
+//! Every value implements [`WritableText`] so it can be passed anywhere text is accepted without formatting it first:
+//! ```rust
+//! use sledgehammer_encoder::css::{deg, percent, px, rgba, translate, rotate};
+//! use sledgehammer_encoder::{batch::Batch, MaybeId, Style};
+//!
+//! let mut batch = Batch::default();
+//! batch.set_style(Style::transform, &[translate(px(12.5), px(40.)), rotate(deg(45.))][..], MaybeId::LastNode);
+//! batch.set_style(Style::width, percent(100.) - px(20.), MaybeId::LastNode);
+//! batch.set_style(Style::color, rgba(255, 0, 0, 0.5), MaybeId::LastNode);
+//! ```
+//!
+//! Numbers are written with at most four decimal places.
+
+use core::ops::{Add, Div, Mul, Sub};
+
+use crate::{
+    buffer::{Buffer, BufferFull},
+    WritableText,
+};
+
+/// The number of decimal places numbers are written with
+const PRECISION: u64 = 10_000;
+
+/// Every f32 at least this large is an integer, so it does not need to be rounded
+const FIRST_INTEGER_ONLY: f32 = 16_777_216.;
+
+/// A unit of length
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    Px,
+    Em,
+    Rem,
+    Percent,
+    Vw,
+    Vh,
+    Vmin,
+    Vmax,
+    Ch,
+    Fr,
+}
+
+impl LengthUnit {
+    /// The unit as it is written in css
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LengthUnit::Px => "px",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Percent => "%",
+            LengthUnit::Vw => "vw",
+            LengthUnit::Vh => "vh",
+            LengthUnit::Vmin => "vmin",
+            LengthUnit::Vmax => "vmax",
+            LengthUnit::Ch => "ch",
+            LengthUnit::Fr => "fr",
+        }
+    }
+}
+
+/// A length like `12.5px` or `100%`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    pub value: f32,
+    pub unit: LengthUnit,
+}
+
+impl Length {
+    pub const fn new(value: f32, unit: LengthUnit) -> Self {
+        Self { value, unit }
+    }
+}
+
+/// A length in pixels
+pub const fn px(value: f32) -> Length {
+    Length::new(value, LengthUnit::Px)
+}
+
+/// A length relative to the font size of the element
+pub const fn em(value: f32) -> Length {
+    Length::new(value, LengthUnit::Em)
+}
+
+/// A length relative to the font size of the root element
+pub const fn rem(value: f32) -> Length {
+    Length::new(value, LengthUnit::Rem)
+}
+
+/// A percentage
+pub const fn percent(value: f32) -> Length {
+    Length::new(value, LengthUnit::Percent)
+}
+
+/// A length relative to the width of the viewport
+pub const fn vw(value: f32) -> Length {
+    Length::new(value, LengthUnit::Vw)
+}
+
+/// A length relative to the height of the viewport
+pub const fn vh(value: f32) -> Length {
+    Length::new(value, LengthUnit::Vh)
+}
+
+/// A fraction of the free space in a grid
+pub const fn fr(value: f32) -> Length {
+    Length::new(value, LengthUnit::Fr)
+}
+
+impl WritableText for Length {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        write_number(self.value, to)?;
+        to.try_extend_from_slice(self.unit.as_str().as_bytes())
+    }
+}
+
+/// A unit of angle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AngleUnit {
+    Deg,
+    Rad,
+    Turn,
+}
+
+impl AngleUnit {
+    /// The unit as it is written in css
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+}
+
+/// An angle like `45deg`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Angle {
+    pub value: f32,
+    pub unit: AngleUnit,
+}
+
+impl Angle {
+    pub const fn new(value: f32, unit: AngleUnit) -> Self {
+        Self { value, unit }
+    }
+}
+
+/// An angle in degrees
+pub const fn deg(value: f32) -> Angle {
+    Angle::new(value, AngleUnit::Deg)
+}
+
+/// An angle in radians
+pub const fn rad(value: f32) -> Angle {
+    Angle::new(value, AngleUnit::Rad)
+}
+
+/// An angle in full turns
+pub const fn turn(value: f32) -> Angle {
+    Angle::new(value, AngleUnit::Turn)
+}
+
+impl WritableText for Angle {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        write_number(self.value, to)?;
+        to.try_extend_from_slice(self.unit.as_str().as_bytes())
+    }
+}
+
+/// A color
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    /// An opaque color written as `#rrggbb`
+    Rgb(u8, u8, u8),
+    /// A color with an alpha channel between 0 and 1 written as `rgba(r, g, b, a)`
+    Rgba(u8, u8, u8, f32),
+    /// A hue in degrees with a saturation and lightness in percent written as `hsl(h, s%, l%)`
+    Hsl(f32, f32, f32),
+    /// A hue in degrees with a saturation and lightness in percent and an alpha channel between 0 and 1 written as `hsla(h, s%, l%, a)`
+    Hsla(f32, f32, f32, f32),
+}
+
+/// An opaque color
+pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+/// A color with an alpha channel between 0 and 1
+pub const fn rgba(r: u8, g: u8, b: u8, a: f32) -> Color {
+    Color::Rgba(r, g, b, a)
+}
+
+/// A color from a hue in degrees with a saturation and lightness in percent
+pub const fn hsl(h: f32, s: f32, l: f32) -> Color {
+    Color::Hsl(h, s, l)
+}
+
+/// A color from a hue in degrees with a saturation and lightness in percent and an alpha channel between 0 and 1
+pub const fn hsla(h: f32, s: f32, l: f32, a: f32) -> Color {
+    Color::Hsla(h, s, l, a)
+}
+
+impl WritableText for Color {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        match self {
+            Color::Rgb(r, g, b) => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                let mut hex = [b'#'; 7];
+                for (i, c) in [r, g, b].into_iter().enumerate() {
+                    hex[1 + i * 2] = HEX[(c >> 4) as usize];
+                    hex[2 + i * 2] = HEX[(c & 0xF) as usize];
+                }
+                to.try_extend_from_slice(&hex)
+            }
+            Color::Rgba(r, g, b, a) => {
+                to.try_extend_from_slice(b"rgba(")?;
+                r.write_as_text(to)?;
+                to.try_extend_from_slice(b", ")?;
+                g.write_as_text(to)?;
+                to.try_extend_from_slice(b", ")?;
+                b.write_as_text(to)?;
+                to.try_extend_from_slice(b", ")?;
+                write_number(a, to)?;
+                to.try_push(b')')
+            }
+            Color::Hsl(h, s, l) => {
+                to.try_extend_from_slice(b"hsl(")?;
+                write_hsl(h, s, l, to)?;
+                to.try_push(b')')
+            }
+            Color::Hsla(h, s, l, a) => {
+                to.try_extend_from_slice(b"hsla(")?;
+                write_hsl(h, s, l, to)?;
+                to.try_extend_from_slice(b", ")?;
+                write_number(a, to)?;
+                to.try_push(b')')
+            }
+        }
+    }
+}
+
+fn write_hsl(h: f32, s: f32, l: f32, to: &mut impl Buffer) -> Result<(), BufferFull> {
+    write_number(h, to)?;
+    to.try_extend_from_slice(b", ")?;
+    write_number(s, to)?;
+    to.try_extend_from_slice(b"%, ")?;
+    write_number(l, to)?;
+    to.try_push(b'%')
+}
+
+/// A transform function. A slice of transform functions is written separated by spaces.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transform {
+    Translate(Length, Length),
+    TranslateX(Length),
+    TranslateY(Length),
+    Scale(f32, f32),
+    Rotate(Angle),
+    SkewX(Angle),
+    SkewY(Angle),
+    Matrix([f32; 6]),
+}
+
+/// Move an element horizontally and vertically
+pub const fn translate(x: Length, y: Length) -> Transform {
+    Transform::Translate(x, y)
+}
+
+/// Scale an element horizontally and vertically
+pub const fn scale(x: f32, y: f32) -> Transform {
+    Transform::Scale(x, y)
+}
+
+/// Rotate an element clockwise
+pub const fn rotate(angle: Angle) -> Transform {
+    Transform::Rotate(angle)
+}
+
+impl WritableText for Transform {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        match self {
+            Transform::Translate(x, y) => {
+                to.try_extend_from_slice(b"translate(")?;
+                x.write_as_text(to)?;
+                to.try_extend_from_slice(b", ")?;
+                y.write_as_text(to)?;
+            }
+            Transform::TranslateX(x) => {
+                to.try_extend_from_slice(b"translateX(")?;
+                x.write_as_text(to)?;
+            }
+            Transform::TranslateY(y) => {
+                to.try_extend_from_slice(b"translateY(")?;
+                y.write_as_text(to)?;
+            }
+            Transform::Scale(x, y) => {
+                to.try_extend_from_slice(b"scale(")?;
+                write_number(x, to)?;
+                to.try_extend_from_slice(b", ")?;
+                write_number(y, to)?;
+            }
+            Transform::Rotate(angle) => {
+                to.try_extend_from_slice(b"rotate(")?;
+                angle.write_as_text(to)?;
+            }
+            Transform::SkewX(angle) => {
+                to.try_extend_from_slice(b"skewX(")?;
+                angle.write_as_text(to)?;
+            }
+            Transform::SkewY(angle) => {
+                to.try_extend_from_slice(b"skewY(")?;
+                angle.write_as_text(to)?;
+            }
+            Transform::Matrix(values) => {
+                to.try_extend_from_slice(b"matrix(")?;
+                for (i, value) in values.into_iter().enumerate() {
+                    if i > 0 {
+                        to.try_extend_from_slice(b", ")?;
+                    }
+                    write_number(value, to)?;
+                }
+            }
+        }
+        to.try_push(b')')
+    }
+}
+
+impl WritableText for &[Transform] {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        for (i, transform) in self.iter().enumerate() {
+            if i > 0 {
+                to.try_push(b' ')?;
+            }
+            transform.write_as_text(to)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `calc()` expression. Calc expressions are created by adding or subtracting lengths or by multiplying or dividing a length by a number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Calc {
+    Add(Length, Length),
+    Sub(Length, Length),
+    Mul(Length, f32),
+    Div(Length, f32),
+}
+
+impl Add for Length {
+    type Output = Calc;
+
+    fn add(self, rhs: Length) -> Calc {
+        Calc::Add(self, rhs)
+    }
+}
+
+impl Sub for Length {
+    type Output = Calc;
+
+    fn sub(self, rhs: Length) -> Calc {
+        Calc::Sub(self, rhs)
+    }
+}
+
+impl Mul<f32> for Length {
+    type Output = Calc;
+
+    fn mul(self, rhs: f32) -> Calc {
+        Calc::Mul(self, rhs)
+    }
+}
+
+impl Div<f32> for Length {
+    type Output = Calc;
+
+    fn div(self, rhs: f32) -> Calc {
+        Calc::Div(self, rhs)
+    }
+}
+
+impl WritableText for Calc {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        to.try_extend_from_slice(b"calc(")?;
+        match self {
+            Calc::Add(lhs, rhs) => {
+                lhs.write_as_text(to)?;
+                to.try_extend_from_slice(b" + ")?;
+                rhs.write_as_text(to)?;
+            }
+            Calc::Sub(lhs, rhs) => {
+                lhs.write_as_text(to)?;
+                to.try_extend_from_slice(b" - ")?;
+                rhs.write_as_text(to)?;
+            }
+            Calc::Mul(lhs, rhs) => {
+                lhs.write_as_text(to)?;
+                to.try_extend_from_slice(b" * ")?;
+                write_number(rhs, to)?;
+            }
+            Calc::Div(lhs, rhs) => {
+                lhs.write_as_text(to)?;
+                to.try_extend_from_slice(b" / ")?;
+                write_number(rhs, to)?;
+            }
+        }
+        to.try_push(b')')
+    }
+}
+
+/// Writes a number with at most four decimal places and without trailing zeros. Numbers that are not finite are written as `0`.
+fn write_number(value: f32, to: &mut impl Buffer) -> Result<(), BufferFull> {
+    if !value.is_finite() {
+        return to.try_push(b'0');
+    }
+    let abs = if value < 0. { -value } else { value };
+    if abs >= FIRST_INTEGER_ONLY {
+        // the scaled value could overflow a u64
+        return value.write_as_text(to);
+    }
+    // round half away from zero to the precision
+    let scaled = (abs as f64 * PRECISION as f64 + 0.5) as u64;
+    if scaled == 0 {
+        return to.try_push(b'0');
+    }
+    if value < 0. {
+        to.try_push(b'-')?;
+    }
+    (scaled / PRECISION).write_as_text(to)?;
+    let mut fraction = scaled % PRECISION;
+    if fraction != 0 {
+        to.try_push(b'.')?;
+        let mut digit = PRECISION / 10;
+        while fraction != 0 {
+            to.try_push(b'0' + (fraction / digit) as u8)?;
+            fraction %= digit;
+            digit /= 10;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn text(value: impl WritableText) -> Vec<u8> {
+        let mut buf = Vec::new();
+        value.write_as_text(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn rgb_is_written_as_hex() {
+        assert_eq!(text(rgb(0, 0, 0)), b"#000000");
+        assert_eq!(text(rgb(255, 16, 9)), b"#ff1009");
+        assert_eq!(text(rgb(0xab, 0xcd, 0xef)), b"#abcdef");
+    }
+
+    #[test]
+    fn colors_with_alpha_or_hue() {
+        assert_eq!(text(rgba(255, 0, 10, 0.5)), b"rgba(255, 0, 10, 0.5)");
+        assert_eq!(text(hsl(120., 50., 25.5)), b"hsl(120, 50%, 25.5%)");
+        assert_eq!(text(hsla(-30., 0., 100., 1.)), b"hsla(-30, 0%, 100%, 1)");
+    }
+
+    #[test]
+    fn numbers_are_rounded_to_four_decimal_places() {
+        assert_eq!(text(px(0.)), b"0px");
+        assert_eq!(text(px(12.5)), b"12.5px");
+        assert_eq!(text(px(1.23456)), b"1.2346px");
+        assert_eq!(text(px(0.00004)), b"0px");
+        assert_eq!(text(px(0.00006)), b"0.0001px");
+        assert_eq!(text(px(9.99999)), b"10px");
+        assert_eq!(text(percent(100.)), b"100%");
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_eq!(text(px(-12.5)), b"-12.5px");
+        assert_eq!(text(deg(-1.23456)), b"-1.2346deg");
+        // values that round to zero are written without a sign
+        assert_eq!(text(px(-0.00001)), b"0px");
+        assert_eq!(text(px(-0.)), b"0px");
+    }
+
+    #[test]
+    fn large_and_non_finite_numbers() {
+        assert_eq!(text(px(16_777_215.)), b"16777215px");
+        assert_eq!(text(px(16_777_216.)), b"16777216px");
+        assert_eq!(text(px(1e20)), b"100000000000000000000px");
+        assert_eq!(text(px(-1e20)), b"-100000000000000000000px");
+        assert_eq!(
+            text(px(f32::MAX)),
+            b"340282350000000000000000000000000000000px"
+        );
+        assert_eq!(text(px(f32::NAN)), b"0px");
+        assert_eq!(text(px(f32::INFINITY)), b"0px");
+        assert_eq!(text(px(f32::NEG_INFINITY)), b"0px");
+    }
+
+    #[test]
+    fn calc_expressions() {
+        assert_eq!(text(percent(100.) - px(20.)), b"calc(100% - 20px)");
+        assert_eq!(text(em(1.5) + px(-2.)), b"calc(1.5em + -2px)");
+        assert_eq!(text(vw(10.) * 2.5), b"calc(10vw * 2.5)");
+        assert_eq!(text(vh(100.) / 3.), b"calc(100vh / 3)");
+    }
+
+    #[test]
+    fn transforms_are_separated_by_spaces() {
+        assert_eq!(
+            text(
+                &[
+                    translate(px(12.5), px(40.)),
+                    rotate(deg(45.)),
+                    scale(2., 0.5)
+                ][..]
+            ),
+            b"translate(12.5px, 40px) rotate(45deg) scale(2, 0.5)"
+        );
+        assert_eq!(
+            text(Transform::Matrix([1., 0., 0., 1., -3., 4.25])),
+            b"matrix(1, 0, 0, 1, -3, 4.25)"
+        );
+    }
+}
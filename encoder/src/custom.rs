@@ -0,0 +1,213 @@
+//! Application specific element names, attribute names and namespaces that are encoded with a single byte.
+//!
+//! Custom names are numbered after the built-in [`Element`], [`Attribute`] and [`KnownNamespace`] tables in the order they are added. The interpreter must register the same names in the same order before any of them are used, see `MsgChannel::with_custom_names`.
+
+use alloc::vec::Vec;
+
+use crate::{
+    attribute::CustomAttribute,
+    element::CustomElement,
+    extended::EXTENDED,
+    namespace::{CustomNamespace, STR_NAMESPACE},
+    prefixed::ARIA_PREFIX,
+    Attribute, Element, KnownNamespace,
+};
+
+/// The first byte that is reserved for names that are not encoded with a single byte
+const FIRST_RESERVED: usize = EXTENDED as usize;
+
+/// The first byte that is reserved for attributes that are not encoded with a single byte
+const FIRST_RESERVED_ATTRIBUTE: usize = ARIA_PREFIX as usize;
+
+/// A dictionary of application specific element names, attribute names and namespaces
+///
+/// Example:
+/// ```rust
+/// use sledgehammer_encoder::{batch::Batch, CustomNames, MaybeId};
+///
+/// let mut names = CustomNames::new();
+/// let grid_cell = names.element("my-grid-cell");
+/// let row_id = names.attribute("data-row-id");
+///
+/// // the interpreter must know the names, see `MsgChannel::with_custom_names`
+/// let mut batch = Batch::default();
+/// // both names are encoded with a single byte
+/// batch.create_element(grid_cell, None);
+/// batch.set_attribute(row_id, 7, MaybeId::LastNode);
+/// let batch = batch.finalize();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CustomNames<'a> {
+    elements: Vec<&'a str>,
+    attributes: Vec<&'a str>,
+    namespaces: Vec<&'a str>,
+}
+
+impl<'a> CustomNames<'a> {
+    /// Create an empty dictionary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of custom elements
+    pub const MAX_ELEMENTS: usize = FIRST_RESERVED - Element::ALL.len();
+
+    /// The maximum number of custom attributes
+    pub const MAX_ATTRIBUTES: usize = FIRST_RESERVED_ATTRIBUTE - Attribute::ALL.len();
+
+    /// The maximum number of custom namespaces
+    pub const MAX_NAMESPACES: usize = STR_NAMESPACE as usize - KnownNamespace::ALL.len();
+
+    /// Add an element name to the dictionary. Adding a name that is already in the dictionary returns the existing element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`CustomNames::MAX_ELEMENTS`] elements are added.
+    pub fn element(&mut self, name: &'a str) -> CustomElement {
+        let idx = add_name(&mut self.elements, name, Self::MAX_ELEMENTS);
+        CustomElement((Element::ALL.len() + idx) as u8)
+    }
+
+    /// Add an attribute name to the dictionary. Adding a name that is already in the dictionary returns the existing attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`CustomNames::MAX_ATTRIBUTES`] attributes are added.
+    pub fn attribute(&mut self, name: &'a str) -> CustomAttribute {
+        let idx = add_name(&mut self.attributes, name, Self::MAX_ATTRIBUTES);
+        CustomAttribute((Attribute::ALL.len() + idx) as u8)
+    }
+
+    /// Add a namespace to the dictionary. Adding a namespace that is already in the dictionary returns the existing namespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`CustomNames::MAX_NAMESPACES`] namespaces are added.
+    pub fn namespace(&mut self, uri: &'a str) -> CustomNamespace {
+        let idx = add_name(&mut self.namespaces, uri, Self::MAX_NAMESPACES);
+        CustomNamespace((KnownNamespace::ALL.len() + idx) as u8)
+    }
+
+    /// Find an element that was added to the dictionary
+    pub fn get_element(&self, name: &str) -> Option<CustomElement> {
+        self.elements
+            .iter()
+            .position(|n| *n == name)
+            .map(|idx| CustomElement((Element::ALL.len() + idx) as u8))
+    }
+
+    /// Find an attribute that was added to the dictionary
+    pub fn get_attribute(&self, name: &str) -> Option<CustomAttribute> {
+        self.attributes
+            .iter()
+            .position(|n| *n == name)
+            .map(|idx| CustomAttribute((Attribute::ALL.len() + idx) as u8))
+    }
+
+    /// Find a namespace that was added to the dictionary
+    pub fn get_namespace(&self, uri: &str) -> Option<CustomNamespace> {
+        self.namespaces
+            .iter()
+            .position(|n| *n == uri)
+            .map(|idx| CustomNamespace((KnownNamespace::ALL.len() + idx) as u8))
+    }
+
+    /// The name of a custom element
+    pub fn element_name(&self, element: CustomElement) -> Option<&'a str> {
+        let idx = (element.0 as usize).checked_sub(Element::ALL.len())?;
+        self.elements.get(idx).copied()
+    }
+
+    /// The name of a custom attribute
+    pub fn attribute_name(&self, attribute: CustomAttribute) -> Option<&'a str> {
+        let idx = (attribute.0 as usize).checked_sub(Attribute::ALL.len())?;
+        self.attributes.get(idx).copied()
+    }
+
+    /// The uri of a custom namespace
+    pub fn namespace_uri(&self, namespace: CustomNamespace) -> Option<&'a str> {
+        let idx = (namespace.0 as usize).checked_sub(KnownNamespace::ALL.len())?;
+        self.namespaces.get(idx).copied()
+    }
+
+    /// The element names in the order they were added
+    pub fn elements(&self) -> &[&'a str] {
+        &self.elements
+    }
+
+    /// The attribute names in the order they were added
+    pub fn attributes(&self) -> &[&'a str] {
+        &self.attributes
+    }
+
+    /// The namespace uris in the order they were added
+    pub fn namespaces(&self) -> &[&'a str] {
+        &self.namespaces
+    }
+}
+
+/// Add a name to a list of names if it is not already in it and return the index of the name
+fn add_name<'a>(names: &mut Vec<&'a str>, name: &'a str, max: usize) -> usize {
+    match names.iter().position(|n| *n == name) {
+        Some(idx) => idx,
+        None => {
+            assert!(
+                names.len() < max,
+                "too many custom names, at most {} can be added",
+                max
+            );
+            names.push(name);
+            names.len() - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, string::String};
+
+    #[test]
+    fn custom_names_are_numbered_after_the_built_in_tables() {
+        let mut names = CustomNames::new();
+        assert_eq!(names.element("a-b").code() as usize, Element::ALL.len());
+        assert_eq!(names.element("c-d").code() as usize, Element::ALL.len() + 1);
+        assert_eq!(names.element("a-b").code() as usize, Element::ALL.len());
+        assert_eq!(
+            names.attribute("data-a").code() as usize,
+            Attribute::ALL.len()
+        );
+        assert_eq!(
+            names.namespace("urn:a").code() as usize,
+            KnownNamespace::ALL.len()
+        );
+        let cell = names.element("c-d");
+        assert_eq!(names.get_element("c-d"), Some(cell));
+        assert_eq!(names.element_name(cell), Some("c-d"));
+    }
+
+    #[test]
+    fn the_last_custom_element_is_before_the_reserved_bytes() {
+        let all: Vec<String> = (0..CustomNames::MAX_ELEMENTS)
+            .map(|i| format!("x-{}", i))
+            .collect();
+        let mut names = CustomNames::new();
+        let mut last = None;
+        for name in &all {
+            last = Some(names.element(name));
+        }
+        assert_eq!(last.unwrap().code() as usize, FIRST_RESERVED - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many custom names")]
+    fn adding_too_many_elements_panics() {
+        let all: Vec<String> = (0..=CustomNames::MAX_ELEMENTS)
+            .map(|i| format!("x-{}", i))
+            .collect();
+        let mut names = CustomNames::new();
+        for name in &all {
+            names.element(name);
+        }
+    }
+}
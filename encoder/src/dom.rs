@@ -0,0 +1,189 @@
+/// Build an element tree from runtime values and add it to a [`Batch`](crate::batch::Batch) or `MsgChannel`
+///
+/// Unlike the `html!` macro from the prebuild crate, every tag, attribute, value, and child can be a runtime expression. The macro expands into direct calls on the batch without allocating: the children of an element are created first and pushed onto the node stack with `push_root`, then the element is created, its attributes are set, and the children are appended with a single `append_children`. The new element is the last node afterwards.
+///
+/// The syntax is:
+/// - An element is a tag followed by optional attributes in parentheses and optional children in braces: `div(class = "list") { ... }`
+/// - A tag is either an [`Element`](crate::Element) like `div`, a string literal like `"my-element"`, or any expression in parentheses like `(tag_name)`
+/// - An attribute is either an [`Attribute`](crate::Attribute) like `class = value`, a string literal like `"data-index" = value`, or any expression in parentheses like `(attr) = value`. The value can be anything that implements [`WritableText`](crate::WritableText)
+/// - `id = expr` sets the [`NodeId`](crate::NodeId) of the element instead of the html id attribute. Use `"id" = value` to set the html id attribute
+/// - A child is an element, a string literal, `text(expr)` for a text node with any [`WritableText`](crate::WritableText), `text(expr, id = expr)` for a text node with a [`NodeId`](crate::NodeId), or `for pattern in iter { ... }` to add children for every item in `iter`. The iterator must be a single identifier or an expression in parentheses
+/// - Children may be separated by commas
+///
+/// Children are created before their parent, so the expressions of the children are evaluated before the attributes of the parent. An element can have at most 65535 children.
+///
+/// Example:
+/// ```rust
+/// use sledgehammer_encoder::{batch::Batch, dom, NodeId};
+///
+/// let mut batch = Batch::default();
+/// let items = ["one", "two", "three"];
+/// let highlighted = 1;
+///
+/// dom!(batch, ul(id = NodeId(1), class = "list") {
+///     for (i, item) in (items.iter().enumerate()) {
+///         li("data-index" = i + 1) {
+///             text(*item)
+///         }
+///     }
+///     p(style = format_args!("color: {}", if highlighted > 0 { "red" } else { "blue" })) {
+///         "Highlighted item: "
+///         text(highlighted, id = NodeId(2))
+///     }
+/// });
+/// let batch = batch.finalize();
+/// ```
+#[macro_export]
+macro_rules! dom {
+    ($target:expr, $tag:tt $(($($attrs:tt)*))? $({$($children:tt)*})?) => {{
+        let target = &mut $target;
+        $crate::dom!(@element target $tag ($($($attrs)*)?) {$($($children)*)?});
+    }};
+
+    (@element $target:ident $tag:tt ($($attrs:tt)*) {$($children:tt)*}) => {{
+        #[allow(unused_mut)]
+        let mut children: u16 = 0;
+        $crate::dom!(@children $target children $($children)*);
+        $target.create_element($crate::dom!(@tag $tag), $crate::dom!(@id $($attrs)*));
+        $crate::dom!(@attrs $target $($attrs)*);
+        if children > 0 {
+            $target.append_children($crate::MaybeId::LastNode, children);
+        }
+    }};
+
+    (@tag $tag:ident) => {
+        $crate::Element::$tag
+    };
+    (@tag $tag:literal) => {
+        $tag
+    };
+    (@tag ($tag:expr)) => {
+        $tag
+    };
+
+    // finds the node id of the element in the attributes
+    (@id) => {
+        None
+    };
+    (@id id = $value:expr $(, $($rest:tt)*)?) => {
+        Some($value)
+    };
+    (@id $name:tt = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::dom!(@id $($($rest)*)?)
+    };
+
+    (@attrs $target:ident) => {};
+    (@attrs $target:ident id = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::dom!(@attrs $target $($($rest)*)?);
+    };
+    (@attrs $target:ident $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $target.set_attribute($crate::Attribute::$name, $value, $crate::MaybeId::LastNode);
+        $crate::dom!(@attrs $target $($($rest)*)?);
+    };
+    (@attrs $target:ident $name:literal = $value:expr $(, $($rest:tt)*)?) => {
+        $target.set_attribute($name, $value, $crate::MaybeId::LastNode);
+        $crate::dom!(@attrs $target $($($rest)*)?);
+    };
+    (@attrs $target:ident ($name:expr) = $value:expr $(, $($rest:tt)*)?) => {
+        $target.set_attribute($name, $value, $crate::MaybeId::LastNode);
+        $crate::dom!(@attrs $target $($($rest)*)?);
+    };
+
+    // pushes the last node onto the node stack as a child of the current element
+    (@push $target:ident $children:ident) => {
+        $target.push_root($crate::MaybeId::LastNode);
+        $children = $children
+            .checked_add(1)
+            .expect("an element can have at most 65535 children");
+    };
+
+    (@children $target:ident $n:ident) => {};
+    (@children $target:ident $n:ident , $($rest:tt)*) => {
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident for $pat:pat in $iter:tt {$($body:tt)*} $($rest:tt)*) => {
+        #[allow(unused_parens)]
+        let iter = $iter;
+        for $pat in iter {
+            $crate::dom!(@children $target $n $($body)*);
+        }
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident text($value:expr) $($rest:tt)*) => {
+        $target.create_text_node($value, None);
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident text($value:expr, id = $id:expr) $($rest:tt)*) => {
+        $target.create_text_node($value, Some($id));
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident $tag:tt ($($attrs:tt)*) {$($children:tt)*} $($rest:tt)*) => {
+        $crate::dom!(@element $target $tag ($($attrs)*) {$($children)*});
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident $tag:tt ($($attrs:tt)*) $($rest:tt)*) => {
+        $crate::dom!(@element $target $tag ($($attrs)*) {});
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident $tag:tt {$($children:tt)*} $($rest:tt)*) => {
+        $crate::dom!(@element $target $tag () {$($children)*});
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident $text:literal $($rest:tt)*) => {
+        $target.create_text_node($text, None);
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+    (@children $target:ident $n:ident $tag:tt $($rest:tt)*) => {
+        $crate::dom!(@element $target $tag () {});
+        $crate::dom!(@push $target $n);
+        $crate::dom!(@children $target $n $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{batch::Batch, Attribute, Element, MaybeId, NodeId};
+
+    #[test]
+    fn expands_into_direct_calls() {
+        let items = ["one", "two"];
+        let mut batch = Batch::default();
+        dom!(batch, ul(class = "list", id = NodeId(1)) {
+            for item in items {
+                li { text(item) }
+            }
+            "end",
+            text(3, id = NodeId(2))
+            br
+        });
+
+        let mut expected = Batch::default();
+        for item in items {
+            expected.create_text_node(item, None);
+            expected.push_root(MaybeId::LastNode);
+            expected.create_element(Element::li, None);
+            expected.append_children(MaybeId::LastNode, 1);
+            expected.push_root(MaybeId::LastNode);
+        }
+        expected.create_text_node("end", None);
+        expected.push_root(MaybeId::LastNode);
+        expected.create_text_node(3, Some(NodeId(2)));
+        expected.push_root(MaybeId::LastNode);
+        expected.create_element(Element::br, None);
+        expected.push_root(MaybeId::LastNode);
+        expected.create_element(Element::ul, Some(NodeId(1)));
+        expected.set_attribute(Attribute::class, "list", MaybeId::LastNode);
+        expected.append_children(MaybeId::LastNode, 5);
+
+        let batch = batch.finalize();
+        let expected = expected.finalize();
+        assert_eq!(batch.msg, expected.msg);
+        assert_eq!(batch.str, expected.str);
+    }
+}
@@ -1,6 +1,14 @@
 #![allow(non_camel_case_types)]
 
-use crate::{attribute::AnyAttribute, batch::Batch, InNamespace, NodeId};
+use alloc::vec::Vec;
+
+use crate::{
+    attribute::AnyAttribute,
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+    extended::{MathMlElement, SvgElement},
+    InNamespace, IntoAttribue, NodeId, WritableText,
+};
 
 use self::sealed::Sealed;
 
@@ -10,6 +18,9 @@ mod sealed {
     pub trait Sealed {}
 
     impl Sealed for Element {}
+    impl Sealed for crate::element::CustomElement {}
+    impl Sealed for crate::extended::SvgElement {}
+    impl Sealed for crate::extended::MathMlElement {}
     impl<'a> Sealed for &'a str {}
     impl<'a> Sealed for InNamespace<'a, Element> {}
     impl<'a, 'b> Sealed for InNamespace<'a, &'b str> {}
@@ -17,24 +28,36 @@ mod sealed {
 
 pub enum AnyElement<'a, 'b> {
     Element(Element),
+    Custom(CustomElement),
+    Svg(SvgElement),
+    MathMl(MathMlElement),
     InNamespace(InNamespace<'a, Element>),
     Str(&'a str),
     InNamespaceStr(InNamespace<'a, &'b str>),
 }
 
 impl AnyElement<'_, '_> {
-    pub fn encode(&self, v: &mut Batch) {
+    pub fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         match self {
             AnyElement::Element(a) => a.encode(v),
+            AnyElement::Custom(a) => a.encode(v),
+            AnyElement::Svg(a) => a.encode(v),
+            AnyElement::MathMl(a) => a.encode(v),
             AnyElement::InNamespace(a) => a.encode(v),
             AnyElement::Str(a) => a.encode(v),
             AnyElement::InNamespaceStr(a) => a.encode(v),
         }
     }
 
-    pub(crate) unsafe fn encode_prealloc(&self, v: &mut Batch) {
+    pub(crate) unsafe fn encode_prealloc<B: Buffer>(
+        &self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
         match self {
             AnyElement::Element(a) => a.encode_prealloc(v),
+            AnyElement::Custom(a) => a.encode_prealloc(v),
+            AnyElement::Svg(a) => a.encode_prealloc(v),
+            AnyElement::MathMl(a) => a.encode_prealloc(v),
             AnyElement::InNamespace(a) => a.encode_prealloc(v),
             AnyElement::Str(a) => a.encode_prealloc(v),
             AnyElement::InNamespaceStr(a) => a.encode_prealloc(v),
@@ -43,10 +66,11 @@ impl AnyElement<'_, '_> {
 
     pub(crate) fn size(&self) -> usize {
         match self {
-            AnyElement::Element(_) => 1,
-            AnyElement::InNamespace(_) => 1 + 2,
-            AnyElement::Str(_) => 2,
-            AnyElement::InNamespaceStr(_) => 2 + 2,
+            AnyElement::Element(_) | AnyElement::Custom(_) => 1,
+            AnyElement::Svg(_) | AnyElement::MathMl(_) => 2,
+            AnyElement::InNamespace(e) => 1 + 1 + e.1.size(),
+            AnyElement::Str(_) => 1 + 2,
+            AnyElement::InNamespaceStr(e) => 1 + 2 + e.1.size(),
         }
     }
 }
@@ -57,17 +81,17 @@ pub trait IntoElement<'a, 'b>: Sealed + Into<AnyElement<'a, 'b>> {
     const SINGLE_BYTE: bool = false;
 
     /// Encode the element into the message channel
-    fn encode(&self, v: &mut Batch);
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull>;
 
     /// Encode the element into the message channel with memory pre-allocated
     /// # Safety
     ///
     /// This is only safe if the batch is preallocated to the correct size
-    unsafe fn encode_prealloc(&self, v: &mut Batch)
+    unsafe fn encode_prealloc<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull>
     where
         Self: Sized,
     {
-        self.encode(v);
+        self.encode(v)
     }
 }
 
@@ -81,21 +105,19 @@ impl<'a, 'b> IntoElement<'a, 'b> for Element {
     const SINGLE_BYTE: bool = true;
 
     #[inline(always)]
-    fn encode(&self, v: &mut Batch) {
-        v.msg.push(*self as u8);
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.msg.try_push(*self as u8)
     }
 
     #[inline(always)]
-    unsafe fn encode_prealloc(&self, v: &mut Batch)
+    unsafe fn encode_prealloc<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull>
     where
         Self: Sized,
     {
         unsafe {
-            let ptr: *mut u8 = v.msg.as_mut_ptr();
-            let old_len = v.msg.len();
-            *ptr.add(old_len) = *self as u8;
-            v.msg.set_len(old_len + 1);
+            v.encode_u8_prealloc(*self as u8);
         }
+        Ok(())
     }
 }
 
@@ -105,6 +127,43 @@ impl<'a, 'b> From<Element> for AnyElement<'a, 'b> {
     }
 }
 
+/// An application specific element name that is encoded with a single byte like an [`Element`]. Custom elements are created with [`CustomNames::element`](crate::custom::CustomNames::element).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomElement(pub(crate) u8);
+
+impl CustomElement {
+    /// The byte the element is encoded as
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+}
+
+impl<'a, 'b> IntoElement<'a, 'b> for CustomElement {
+    const SINGLE_BYTE: bool = true;
+
+    #[inline(always)]
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.msg.try_push(self.0)
+    }
+
+    #[inline(always)]
+    unsafe fn encode_prealloc<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull>
+    where
+        Self: Sized,
+    {
+        unsafe {
+            v.encode_u8_prealloc(self.0);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'b> From<CustomElement> for AnyElement<'a, 'b> {
+    fn from(e: CustomElement) -> Self {
+        AnyElement::Custom(e)
+    }
+}
+
 impl<'a, 'b> InNamespace<'a, Element> {
     /// Turn into an [`AnyElement`] in a const context
     pub const fn any_element_const(self) -> AnyElement<'a, 'b> {
@@ -113,10 +172,9 @@ impl<'a, 'b> InNamespace<'a, Element> {
 }
 
 impl<'a, 'b> IntoElement<'a, 'b> for InNamespace<'a, Element> {
-    fn encode(&self, v: &mut Batch) {
-        v.msg.push(255);
-        v.msg.push(self.0 as u8);
-        v.encode_str(self.1);
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.msg.try_extend_from_slice(&[255, self.0 as u8])?;
+        self.1.encode(v)
     }
 }
 
@@ -127,9 +185,9 @@ impl<'a, 'b> From<InNamespace<'a, Element>> for AnyElement<'a, 'b> {
 }
 
 impl<'a, 'b> IntoElement<'a, 'b> for &'a str {
-    fn encode(&self, v: &mut Batch) {
-        v.msg.push(254);
-        v.encode_str(*self);
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.msg.try_push(254)?;
+        v.encode_str(*self)
     }
 }
 
@@ -140,10 +198,10 @@ impl<'a, 'b> From<&'a str> for AnyElement<'a, 'b> {
 }
 
 impl<'a, 'b> IntoElement<'a, 'b> for InNamespace<'a, &'b str> {
-    fn encode(&self, v: &mut Batch) {
-        v.msg.push(253);
-        v.encode_str(self.0);
-        v.encode_str(self.1);
+    fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.msg.try_push(253)?;
+        v.encode_str(self.0)?;
+        self.1.encode(v)
     }
 }
 
@@ -167,7 +225,7 @@ pub enum NodeBuilder<'a> {
 
 impl NodeBuilder<'_> {
     /// Encode the node into a batch
-    pub(crate) fn encode(&self, v: &mut Batch) {
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         match self {
             NodeBuilder::Text(t) => t.encode(v),
             NodeBuilder::Element(e) => e.encode(v),
@@ -206,17 +264,17 @@ impl<'a> TextBuilder<'a> {
     }
 
     /// Encode the text node into a batch
-    pub(crate) fn encode(&self, v: &mut Batch) {
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         match self.id {
             Some(id) => {
-                v.msg.push(3);
-                v.encode_id(id);
+                v.msg.try_push(3)?;
+                v.encode_id(id)?;
             }
             None => {
-                v.msg.push(2);
+                v.msg.try_push(2)?;
             }
         }
-        v.encode_str(self.text);
+        v.encode_str(self.text)
     }
 }
 
@@ -276,7 +334,7 @@ impl<'a> ElementBuilder<'a> {
     }
 
     /// Encode the element into the a batch
-    pub(crate) fn encode(&self, v: &mut Batch) {
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
         let size = 1
             + (self.id.is_some() as usize) * 4
             + self.kind.size()
@@ -287,7 +345,7 @@ impl<'a> ElementBuilder<'a> {
                 .iter()
                 .map(|(k, _)| k.size_with_u8_discriminant() + 2)
                 .sum::<usize>();
-        v.msg.reserve(size);
+        v.msg.try_reserve(size)?;
         unsafe {
             match self.id {
                 Some(id) => {
@@ -298,55 +356,237 @@ impl<'a> ElementBuilder<'a> {
                     v.encode_u8_prealloc(0);
                 }
             }
-            self.kind.encode_prealloc(v);
+            self.kind.encode_prealloc(v)?;
             // these are packed together so they can be read as a u16
             v.encode_u8_prealloc(self.attrs.len() as u8);
             v.encode_u8_prealloc(self.children.len() as u8);
             for (attr, value) in self.attrs {
-                attr.encode_u8_discriminant_prealloc(v);
-                v.encode_str_prealloc(*value);
+                attr.encode_u8_discriminant_prealloc(v)?;
+                v.encode_str_prealloc(*value)?;
             }
         }
         for child in self.children {
-            child.encode(v);
+            child.encode(v)?;
         }
+        Ok(())
     }
 }
 
-macro_rules! elements {
-    ($($i: ident),*) => {
-        /// All built-in elements
-        /// These are the element can be encoded with a single byte so they are more efficient (but less flexable) than a &str element
-        #[allow(unused)]
-        #[derive(Copy, Clone)]
-        pub enum Element {
-            $(
-                $i
-            ),*
+/// An owned builder for any node
+pub enum OwnedNodeBuilder<'a> {
+    Text(OwnedTextBuilder),
+    Element(OwnedElementBuilder<'a>),
+}
+
+impl OwnedNodeBuilder<'_> {
+    /// Encode the node into a batch
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        match self {
+            OwnedNodeBuilder::Text(t) => t.encode(v),
+            OwnedNodeBuilder::Element(e) => e.encode(v),
         }
+    }
+}
+
+impl<'a> From<OwnedTextBuilder> for OwnedNodeBuilder<'a> {
+    fn from(t: OwnedTextBuilder) -> Self {
+        OwnedNodeBuilder::Text(t)
+    }
+}
+
+impl<'a> From<OwnedElementBuilder<'a>> for OwnedNodeBuilder<'a> {
+    fn from(e: OwnedElementBuilder<'a>) -> Self {
+        OwnedNodeBuilder::Element(e)
+    }
+}
+
+/// An owned builder for a text node with a id, and text. The text is written when the builder is created.
+pub struct OwnedTextBuilder {
+    id: Option<NodeId>,
+    text: Vec<u8>,
+}
 
-        pub struct NotElementError;
+impl OwnedTextBuilder {
+    /// Create a new text builder
+    pub fn new(text: impl WritableText) -> Self {
+        Self {
+            id: None,
+            text: write_owned(text),
+        }
+    }
 
-        impl std::str::FromStr for Element {
-            type Err = NotElementError;
+    /// Set the id of the text node
+    pub fn id(mut self, id: NodeId) -> Self {
+        self.id = Some(id);
+        self
+    }
 
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok(match s{
-                    $(
-                        stringify!($i) => Self::$i,
-                    )*
-                    _ => return Err(NotElementError)
-                })
+    /// Encode the text node into a batch
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        match self.id {
+            Some(id) => {
+                v.msg.try_push(3)?;
+                v.encode_id(id)?;
+            }
+            None => {
+                v.msg.try_push(2)?;
             }
         }
-    };
+        v.encode_raw_str(&self.text)
+    }
+}
+
+/// An owned builder for a element with an id, kind, attributes, and children
+///
+/// Unlike [`ElementBuilder`], the attributes and children are owned by the builder so it can be built at runtime. Attribute values and text are written when they are added.
+/// It is encoded the same way as an [`ElementBuilder`].
+///
+/// An element can have at most 255 attributes and 255 children because the counts are encoded as single bytes.
+///
+/// Example:
+/// ```rust
+/// use sledgehammer_encoder::{batch::Batch, Attribute, Element, NodeId, OwnedElementBuilder};
+///
+/// let mut batch = Batch::default();
+/// let items = ["one", "two", "three"];
+///
+/// let mut list = OwnedElementBuilder::new(Element::ul).id(NodeId(1));
+/// list.push_attr(Attribute::class, "list");
+/// for (i, item) in items.iter().enumerate() {
+///     let mut li = OwnedElementBuilder::new(Element::li);
+///     li.push_attr("data-index", i);
+///     li.text(*item);
+///     list.push_child(li);
+/// }
+/// batch.build_owned_element(&list);
+/// let batch = batch.finalize();
+/// ```
+pub struct OwnedElementBuilder<'a> {
+    id: Option<NodeId>,
+    kind: AnyElement<'a, 'a>,
+    // the length of each attribute's value in `values`
+    attrs: Vec<(AnyAttribute<'a, 'a>, usize)>,
+    values: Vec<u8>,
+    children: Vec<OwnedNodeBuilder<'a>>,
 }
 
-elements! {
+impl<'a> OwnedElementBuilder<'a> {
+    /// Create a new element builder
+    pub fn new(kind: impl IntoElement<'a, 'a>) -> Self {
+        Self {
+            id: None,
+            kind: kind.into(),
+            attrs: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Set the id of the element
+    pub fn id(mut self, id: NodeId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Add an attribute to the element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element already has 255 attributes
+    pub fn push_attr(&mut self, attr: impl IntoAttribue<'a, 'a>, value: impl WritableText) {
+        assert!(
+            self.attrs.len() < u8::MAX as usize,
+            "an element can have at most 255 attributes"
+        );
+        let prev_len = self.values.len();
+        // writing into a vec cannot run out of space
+        let _ = value.write_as_text(&mut self.values);
+        self.attrs.push((attr.into(), self.values.len() - prev_len));
+    }
+
+    /// Add a child node to the element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element already has 255 children
+    pub fn push_child(&mut self, child: impl Into<OwnedNodeBuilder<'a>>) {
+        assert!(
+            self.children.len() < u8::MAX as usize,
+            "an element can have at most 255 children"
+        );
+        self.children.push(child.into());
+    }
+
+    /// Add a text node to the element
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element already has 255 children
+    pub fn text(&mut self, text: impl WritableText) {
+        self.push_child(OwnedTextBuilder::new(text));
+    }
+
+    /// Encode the element into the a batch
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        let size = 1
+            + (self.id.is_some() as usize) * 4
+            + self.kind.size()
+            + 1
+            + 1
+            + self
+                .attrs
+                .iter()
+                .map(|(k, _)| k.size_with_u8_discriminant() + 2)
+                .sum::<usize>();
+        v.msg.try_reserve(size)?;
+        unsafe {
+            match self.id {
+                Some(id) => {
+                    v.encode_u8_prealloc(1);
+                    v.encode_id_prealloc(id);
+                }
+                None => {
+                    v.encode_u8_prealloc(0);
+                }
+            }
+            self.kind.encode_prealloc(v)?;
+            // these are packed together so they can be read as a u16
+            v.encode_u8_prealloc(self.attrs.len() as u8);
+            v.encode_u8_prealloc(self.children.len() as u8);
+            let mut start = 0;
+            for (attr, len) in &self.attrs {
+                attr.encode_u8_discriminant_prealloc(v)?;
+                v.encode_raw_str_prealloc(&self.values[start..start + len])?;
+                start += len;
+            }
+        }
+        for child in &self.children {
+            child.encode(v)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write text into a new vec
+fn write_owned(text: impl WritableText) -> Vec<u8> {
+    let mut buf = Vec::new();
+    // writing into a vec cannot run out of space
+    let _ = text.write_as_text(&mut buf);
+    buf
+}
+
+name_table! {
+    /// All built-in elements
+    /// These are the element can be encoded with a single byte so they are more efficient (but less flexable) than a &str element
+    pub enum Element;
+    /// The error returned when parsing a name that is not a built-in [`Element`]
+    pub struct ParseElementError("not a built-in element");
     a,
     abbr,
+    #[deprecated(note = "obsolete in the html standard")]
     acronym,
     address,
+    #[deprecated(note = "obsolete in the html standard")]
     applet,
     area,
     article,
@@ -356,8 +596,11 @@ elements! {
     base,
     bdi,
     bdo,
+    #[deprecated(note = "obsolete in the html standard")]
     bgsound,
+    #[deprecated(note = "obsolete in the html standard")]
     big,
+    #[deprecated(note = "obsolete in the html standard")]
     blink,
     blockquote,
     body,
@@ -365,11 +608,13 @@ elements! {
     button,
     canvas,
     caption,
+    #[deprecated(note = "obsolete in the html standard")]
     center,
     cite,
     code,
     col,
     colgroup,
+    #[deprecated(note = "obsolete in the html standard")]
     content,
     data,
     datalist,
@@ -378,6 +623,7 @@ elements! {
     details,
     dfn,
     dialog,
+    #[deprecated(note = "obsolete in the html standard")]
     dir,
     div,
     dl,
@@ -387,10 +633,13 @@ elements! {
     fieldset,
     figcaption,
     figure,
+    #[deprecated(note = "obsolete in the html standard")]
     font,
     footer,
     form,
+    #[deprecated(note = "obsolete in the html standard")]
     frame,
+    #[deprecated(note = "obsolete in the html standard")]
     frameset,
     h1,
     head,
@@ -400,11 +649,13 @@ elements! {
     html,
     i,
     iframe,
+    #[deprecated(note = "obsolete in the html standard")]
     image,
     img,
     input,
     ins,
     kbd,
+    #[deprecated(note = "obsolete in the html standard")]
     keygen,
     label,
     legend,
@@ -413,14 +664,19 @@ elements! {
     main,
     map,
     mark,
+    #[deprecated(note = "obsolete in the html standard")]
     marquee,
     menu,
+    #[deprecated(note = "obsolete in the html standard")]
     menuitem,
     meta,
     meter,
     nav,
+    #[deprecated(note = "obsolete in the html standard")]
     nobr,
+    #[deprecated(note = "obsolete in the html standard")]
     noembed,
+    #[deprecated(note = "obsolete in the html standard")]
     noframes,
     noscript,
     object,
@@ -429,16 +685,20 @@ elements! {
     option,
     output,
     p,
+    #[deprecated(note = "obsolete in the html standard")]
     param,
     picture,
+    #[deprecated(note = "obsolete in the html standard")]
     plaintext,
     portal,
     pre,
     progress,
     q,
+    #[deprecated(note = "obsolete in the html standard")]
     rb,
     rp,
     rt,
+    #[deprecated(note = "obsolete in the html standard")]
     rtc,
     ruby,
     s,
@@ -446,12 +706,15 @@ elements! {
     script,
     section,
     select,
+    #[deprecated(note = "obsolete in the html standard")]
     shadow,
     slot,
     small,
     source,
+    #[deprecated(note = "obsolete in the html standard")]
     spacer,
     span,
+    #[deprecated(note = "obsolete in the html standard")]
     strike,
     strong,
     style,
@@ -470,11 +733,88 @@ elements! {
     title,
     tr,
     track,
+    #[deprecated(note = "obsolete in the html standard")]
     tt,
     u,
     ul,
     var,
     video,
     wbr,
-    xmp
+    #[deprecated(note = "obsolete in the html standard")]
+    xmp,
+    h2,
+    h3,
+    h4,
+    h5,
+    h6,
+    search,
+}
+
+/// The old name of [`ParseElementError`]
+#[deprecated(note = "use ParseElementError")]
+pub type NotElementError = ParseElementError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data, Attribute, MaybeId};
+
+    #[test]
+    fn owned_builder_is_encoded_like_the_borrowed_builder() {
+        let mut owned = OwnedElementBuilder::new(Element::ul).id(NodeId(1));
+        owned.push_attr(Attribute::class, "list");
+        owned.push_attr("x-custom", "1");
+        owned.push_attr(data("row"), "2");
+        let mut li = OwnedElementBuilder::new("x-item");
+        li.push_attr(Attribute::title, "first");
+        li.text("one");
+        owned.push_child(li);
+        owned.push_child(OwnedTextBuilder::new("two").id(NodeId(2)));
+        let mut owned_batch = Batch::default();
+        owned_batch.build_owned_element(&owned);
+        owned_batch.set_text("after", MaybeId::LastNode);
+
+        let mut borrowed_batch = Batch::default();
+        borrowed_batch.build_full_element(
+            ElementBuilder::new(Element::ul.any_element_const())
+                .id(NodeId(1))
+                .attrs(&[
+                    (Attribute::class.any_attr_const(), "list"),
+                    (AnyAttribute::Str("x-custom"), "1"),
+                    (data("row").any_attr_const(), "2"),
+                ])
+                .children(&[
+                    NodeBuilder::Element(
+                        ElementBuilder::new(AnyElement::Str("x-item"))
+                            .attrs(&[(Attribute::title.any_attr_const(), "first")])
+                            .children(&[NodeBuilder::Text(TextBuilder::new("one"))]),
+                    ),
+                    NodeBuilder::Text(TextBuilder::new("two").id(NodeId(2))),
+                ]),
+        );
+        borrowed_batch.set_text("after", MaybeId::LastNode);
+
+        let owned_batch = owned_batch.finalize();
+        let borrowed_batch = borrowed_batch.finalize();
+        assert_eq!(owned_batch.msg, borrowed_batch.msg);
+        assert_eq!(owned_batch.str, borrowed_batch.str);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 255 attributes")]
+    fn owned_builder_limits_attributes() {
+        let mut el = OwnedElementBuilder::new(Element::div);
+        for i in 0..256 {
+            el.push_attr(Attribute::title, i);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 255 children")]
+    fn owned_builder_limits_children() {
+        let mut el = OwnedElementBuilder::new(Element::ul);
+        for i in 0..256 {
+            el.text(i);
+        }
+    }
 }
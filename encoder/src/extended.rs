@@ -0,0 +1,442 @@
+//! SVG and MathML names that are encoded with two bytes.
+//!
+//! Single byte codes are used up by the built-in html tables and custom names, so these names are encoded as the [`EXTENDED`] prefix followed by an index into an extended table.
+//! SVG elements are always created in the SVG namespace and MathML elements in the MathML namespace. SVG and MathML attributes are not in a namespace.
+
+#![allow(non_camel_case_types)]
+
+use crate::{
+    attribute::AnyAttribute,
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+    element::AnyElement,
+    IntoAttribue, IntoElement,
+};
+
+/// The byte that marks an element or attribute from an extended table
+pub(crate) const EXTENDED: u8 = 252;
+
+/// The index of the first MathML element in the extended element table
+const FIRST_MATHML_ELEMENT: usize = SvgElement::ALL.len();
+
+/// The index of the first MathML attribute in the extended attribute table
+const FIRST_MATHML_ATTRIBUTE: usize = SvgAttribute::ALL.len();
+
+const _: () = assert!(
+    SvgElement::ALL.len() + MathMlElement::ALL.len() <= 256,
+    "the extended element table must be indexable with a single byte"
+);
+
+const _: () = assert!(
+    SvgAttribute::ALL.len() + MathMlAttribute::ALL.len() <= 256,
+    "the extended attribute table must be indexable with a single byte"
+);
+
+impl SvgElement {
+    /// The index of the element in the extended element table
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl MathMlElement {
+    /// The index of the element in the extended element table
+    pub const fn code(self) -> u8 {
+        (FIRST_MATHML_ELEMENT + self as usize) as u8
+    }
+}
+
+impl SvgAttribute {
+    /// The index of the attribute in the extended attribute table
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl MathMlAttribute {
+    /// The index of the attribute in the extended attribute table
+    pub const fn code(self) -> u8 {
+        (FIRST_MATHML_ATTRIBUTE + self as usize) as u8
+    }
+}
+
+macro_rules! extended_element {
+    ($name:ident, $variant:ident) => {
+        impl<'a, 'b> $name {
+            /// Turn into an [`AnyElement`] in a const context
+            pub const fn any_element_const(self) -> AnyElement<'a, 'b> {
+                AnyElement::$variant(self)
+            }
+        }
+
+        impl<'a, 'b> IntoElement<'a, 'b> for $name {
+            #[inline(always)]
+            fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+                v.msg.try_extend_from_slice(&[EXTENDED, self.code()])
+            }
+        }
+
+        impl<'a, 'b> From<$name> for AnyElement<'a, 'b> {
+            fn from(e: $name) -> Self {
+                AnyElement::$variant(e)
+            }
+        }
+    };
+}
+
+extended_element!(SvgElement, Svg);
+extended_element!(MathMlElement, MathMl);
+
+macro_rules! extended_attribute {
+    ($name:ident, $variant:ident) => {
+        impl<'a, 'b> $name {
+            /// Turn into an [`AnyAttribute`] in a const context
+            pub const fn any_attr_const(self) -> AnyAttribute<'a, 'b> {
+                AnyAttribute::$variant(self)
+            }
+        }
+
+        impl<'a, 'b> IntoAttribue<'a, 'b> for $name {
+            #[inline(always)]
+            fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+                v.encode_bool(false);
+                v.encode_bool(false);
+                v.msg.try_extend_from_slice(&[EXTENDED, self.code()])
+            }
+
+            #[inline(always)]
+            unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+                self,
+                v: &mut Batch<B>,
+            ) -> Result<(), BufferFull> {
+                v.encode_u8_prealloc(EXTENDED);
+                v.encode_u8_prealloc(self.code());
+                Ok(())
+            }
+        }
+
+        impl<'a, 'b> From<$name> for AnyAttribute<'a, 'b> {
+            fn from(a: $name) -> Self {
+                AnyAttribute::$variant(a)
+            }
+        }
+    };
+}
+
+extended_attribute!(SvgAttribute, Svg);
+extended_attribute!(MathMlAttribute, MathMl);
+
+name_table! {
+    /// SVG elements. They are encoded with two bytes and created in the SVG namespace.
+    pub enum SvgElement;
+    /// The error returned when parsing a name that is not an [`SvgElement`]
+    pub struct ParseSvgElementError("not an svg element");
+    svg,
+    a,
+    animate,
+    animateMotion,
+    animateTransform,
+    circle,
+    clipPath,
+    defs,
+    desc,
+    ellipse,
+    feBlend,
+    feColorMatrix,
+    feComponentTransfer,
+    feComposite,
+    feConvolveMatrix,
+    feDiffuseLighting,
+    feDisplacementMap,
+    feDistantLight,
+    feDropShadow,
+    feFlood,
+    feFuncA,
+    feFuncB,
+    feFuncG,
+    feFuncR,
+    feGaussianBlur,
+    feImage,
+    feMerge,
+    feMergeNode,
+    feMorphology,
+    feOffset,
+    fePointLight,
+    feSpecularLighting,
+    feSpotLight,
+    feTile,
+    feTurbulence,
+    filter,
+    foreignObject,
+    g,
+    image,
+    line,
+    linearGradient,
+    marker,
+    mask,
+    metadata,
+    mpath,
+    path,
+    pattern,
+    polygon,
+    polyline,
+    radialGradient,
+    rect,
+    script,
+    set,
+    stop,
+    style,
+    switch,
+    symbol,
+    text,
+    textPath,
+    title,
+    tspan,
+    r#use = "use",
+    view,
+}
+
+name_table! {
+    /// MathML elements. They are encoded with two bytes and created in the MathML namespace.
+    pub enum MathMlElement;
+    /// The error returned when parsing a name that is not a [`MathMlElement`]
+    pub struct ParseMathMlElementError("not a mathml element");
+    math,
+    annotation,
+    annotation_xml = "annotation-xml",
+    maction,
+    menclose,
+    merror,
+    mfrac,
+    mi,
+    mmultiscripts,
+    mn,
+    mo,
+    mover,
+    mpadded,
+    mphantom,
+    mprescripts,
+    mroot,
+    mrow,
+    ms,
+    mspace,
+    msqrt,
+    mstyle,
+    msub,
+    msubsup,
+    msup,
+    mtable,
+    mtd,
+    mtext,
+    mtr,
+    munder,
+    munderover,
+    semantics,
+}
+
+name_table! {
+    /// SVG attributes that are not built-in [`Attribute`](crate::Attribute)s. They are encoded with two bytes.
+    pub enum SvgAttribute;
+    /// The error returned when parsing a name that is not an [`SvgAttribute`]
+    pub struct ParseSvgAttributeError("not an svg attribute");
+    accumulate,
+    additive,
+    alignment_baseline = "alignment-baseline",
+    attributeName,
+    attributeType,
+    baseFrequency,
+    baseline_shift = "baseline-shift",
+    begin,
+    bias,
+    by,
+    calcMode,
+    clip,
+    clip_path = "clip-path",
+    clip_rule = "clip-rule",
+    clipPathUnits,
+    color_interpolation = "color-interpolation",
+    color_interpolation_filters = "color-interpolation-filters",
+    cursor,
+    cx,
+    cy,
+    d,
+    diffuseConstant,
+    direction,
+    display,
+    divisor,
+    dominant_baseline = "dominant-baseline",
+    dur,
+    dx,
+    dy,
+    edgeMode,
+    elevation,
+    end,
+    exponent,
+    fill,
+    fill_opacity = "fill-opacity",
+    fill_rule = "fill-rule",
+    filter,
+    filterUnits,
+    flood_color = "flood-color",
+    flood_opacity = "flood-opacity",
+    font_family = "font-family",
+    font_size = "font-size",
+    font_size_adjust = "font-size-adjust",
+    font_stretch = "font-stretch",
+    font_style = "font-style",
+    font_variant = "font-variant",
+    font_weight = "font-weight",
+    fr,
+    from,
+    fx,
+    fy,
+    gradientTransform,
+    gradientUnits,
+    image_rendering = "image-rendering",
+    r#in = "in",
+    in2,
+    intercept,
+    k1,
+    k2,
+    k3,
+    k4,
+    kernelMatrix,
+    kernelUnitLength,
+    keyPoints,
+    keySplines,
+    keyTimes,
+    lengthAdjust,
+    letter_spacing = "letter-spacing",
+    lighting_color = "lighting-color",
+    limitingConeAngle,
+    marker_end = "marker-end",
+    marker_mid = "marker-mid",
+    marker_start = "marker-start",
+    markerHeight,
+    markerUnits,
+    markerWidth,
+    mask,
+    maskContentUnits,
+    maskUnits,
+    mode,
+    numOctaves,
+    offset,
+    opacity,
+    operator,
+    order,
+    orient,
+    origin,
+    overflow,
+    paint_order = "paint-order",
+    path,
+    pathLength,
+    patternContentUnits,
+    patternTransform,
+    patternUnits,
+    pointer_events = "pointer-events",
+    points,
+    pointsAtX,
+    pointsAtY,
+    pointsAtZ,
+    preserveAlpha,
+    preserveAspectRatio,
+    primitiveUnits,
+    r,
+    radius,
+    refX,
+    refY,
+    repeatCount,
+    repeatDur,
+    restart,
+    result,
+    rotate,
+    rx,
+    ry,
+    scale,
+    seed,
+    shape_rendering = "shape-rendering",
+    specularConstant,
+    specularExponent,
+    spreadMethod,
+    startOffset,
+    stdDeviation,
+    stitchTiles,
+    stop_color = "stop-color",
+    stop_opacity = "stop-opacity",
+    stroke,
+    stroke_dasharray = "stroke-dasharray",
+    stroke_dashoffset = "stroke-dashoffset",
+    stroke_linecap = "stroke-linecap",
+    stroke_linejoin = "stroke-linejoin",
+    stroke_miterlimit = "stroke-miterlimit",
+    stroke_opacity = "stroke-opacity",
+    stroke_width = "stroke-width",
+    surfaceScale,
+    systemLanguage,
+    tableValues,
+    targetX,
+    targetY,
+    text_anchor = "text-anchor",
+    text_decoration = "text-decoration",
+    text_rendering = "text-rendering",
+    textLength,
+    to,
+    transform,
+    transform_origin = "transform-origin",
+    unicode_bidi = "unicode-bidi",
+    values,
+    vector_effect = "vector-effect",
+    viewBox,
+    visibility,
+    word_spacing = "word-spacing",
+    writing_mode = "writing-mode",
+    x,
+    x1,
+    x2,
+    xChannelSelector,
+    y,
+    y1,
+    y2,
+    yChannelSelector,
+    z,
+}
+
+name_table! {
+    /// MathML attributes that are not built-in [`Attribute`](crate::Attribute)s. They are encoded with two bytes.
+    pub enum MathMlAttribute;
+    /// The error returned when parsing a name that is not a [`MathMlAttribute`]
+    pub struct ParseMathMlAttributeError("not a mathml attribute");
+    accent,
+    accentunder,
+    columnalign,
+    columnlines,
+    columnspacing,
+    columnspan,
+    depth,
+    display,
+    displaystyle,
+    encoding,
+    fence,
+    frame,
+    framespacing,
+    largeop,
+    linethickness,
+    lspace,
+    mathbackground,
+    mathcolor,
+    mathsize,
+    mathvariant,
+    maxsize,
+    minsize,
+    movablelimits,
+    notation,
+    rowalign,
+    rowlines,
+    rowspacing,
+    rspace,
+    scriptlevel,
+    separator,
+    stretchy,
+    symmetric,
+    voffset,
+}
@@ -1,25 +1,56 @@
+//! Encodes DOM operations into compact batches that can be run by the sledgehammer interpreter.
+//!
+//! The encoder only needs an allocator. It supports `no_std` environments with `alloc` when the default `std` feature is disabled.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod table;
+
 pub mod attribute;
 pub mod batch;
+pub mod buffer;
+pub mod css;
+pub mod custom;
+mod dom;
 pub mod element;
+pub mod extended;
+pub mod namespace;
+pub mod optimize;
+pub mod prefixed;
+pub mod style;
+
+use alloc::{borrow::Cow, string::String};
+use core::fmt::{Arguments, Display, Write};
 
-use std::{fmt::Arguments, io::Write};
+use buffer::{Buffer, BufferFull};
 
-pub use attribute::{Attribute, IntoAttribue};
-pub use batch::{Op, StaticBatch};
-pub use element::{Element, ElementBuilder, IntoElement, NodeBuilder, TextBuilder};
+pub use attribute::{Attribute, CustomAttribute, IntoAttribue};
+pub use batch::{Op, StaticBatch, StringEncoding};
+pub use custom::CustomNames;
+pub use element::{
+    CustomElement, Element, ElementBuilder, IntoElement, NodeBuilder, OwnedElementBuilder,
+    OwnedNodeBuilder, OwnedTextBuilder, TextBuilder,
+};
+pub use extended::{MathMlAttribute, MathMlElement, SvgAttribute, SvgElement};
+pub use namespace::{CustomNamespace, KnownNamespace, Namespace};
+pub use prefixed::{aria, data, AttributePrefix, PrefixedAttribute};
+pub use style::{IntoStyle, Style};
 
 /// Something that lives in a namespace like a tag or attribute
 #[derive(Clone, Copy)]
-pub struct InNamespace<'a, T>(pub T, pub &'a str);
+pub struct InNamespace<'a, T>(pub T, pub Namespace<'a>);
 
 /// Something that can live in a namespace
 pub trait WithNsExt {
-    /// Moves the item into a namespace
-    fn in_namespace(self, namespace: &str) -> InNamespace<Self>
+    /// Moves the item into a namespace. Well-known namespace uris are encoded with a single byte.
+    fn in_namespace<'a>(self, namespace: impl Into<Namespace<'a>>) -> InNamespace<'a, Self>
     where
         Self: Sized,
     {
-        InNamespace(self, namespace)
+        InNamespace(self, namespace.into())
     }
 }
 
@@ -53,81 +84,118 @@ pub struct NodeId(pub u32);
 
 /// Something that can be written as a utf-8 string to a buffer
 pub trait WritableText {
-    fn write_as_text(self, to: &mut Vec<u8>);
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull>;
 }
 
 impl WritableText for char {
-    fn write_as_text(self, to: &mut Vec<u8>) {
-        to.push(self as u8);
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        self.encode_utf8(&mut [0; 4]).write_as_text(to)
+    }
+}
+
+impl WritableText for bool {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        if self { "true" } else { "false" }.write_as_text(to)
     }
 }
 
 impl<'a> WritableText for &'a str {
     #[inline(always)]
-    fn write_as_text(self, to: &mut Vec<u8>) {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
         let len = self.len();
-        to.reserve(len);
+        to.try_reserve(len)?;
         let old_len = to.len();
-        #[allow(clippy::uninit_vec)]
         unsafe {
-            let ptr = to.as_mut_ptr();
+            let ptr = to.spare_ptr();
             let bytes = self.as_bytes();
             let str_ptr = bytes.as_ptr();
             for o in 0..len {
-                *ptr.add(old_len + o) = *str_ptr.add(o);
+                *ptr.add(o) = *str_ptr.add(o);
             }
             to.set_len(old_len + len);
         }
-        // let _ = to.write(self.as_bytes());
+        Ok(())
+    }
+}
+
+/// Writes formatted text into a [`Buffer`]
+struct BufferWriter<'a, B>(&'a mut B);
+
+impl<B: Buffer> Write for BufferWriter<'_, B> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0
+            .try_extend_from_slice(s.as_bytes())
+            .map_err(|_| core::fmt::Error)
+    }
+}
+
+impl WritableText for String {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        self.as_str().write_as_text(to)
+    }
+}
+
+impl WritableText for &String {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        self.as_str().write_as_text(to)
+    }
+}
+
+impl WritableText for Cow<'_, str> {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        (*self).write_as_text(to)
     }
 }
 
 impl WritableText for Arguments<'_> {
-    fn write_as_text(self, to: &mut Vec<u8>) {
-        let _ = to.write_fmt(self);
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        BufferWriter(to).write_fmt(self).map_err(|_| BufferFull)
+    }
+}
+
+/// Writes any [`Display`] value as text. The value is formatted directly into the string buffer without an intermediate allocation.
+pub struct DisplayText<T>(pub T);
+
+impl<T: Display> WritableText for DisplayText<T> {
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        write!(BufferWriter(to), "{}", self.0).map_err(|_| BufferFull)
     }
 }
 
+/// Writes text with a closure. The closure must write valid utf-8; invalid bytes are replaced with U+FFFD when the text is converted to utf-16
 impl<F> WritableText for F
 where
-    F: FnOnce(&mut Vec<u8>),
+    F: FnOnce(&mut dyn Buffer) -> Result<(), BufferFull>,
 {
-    fn write_as_text(self, to: &mut Vec<u8>) {
-        self(to);
+    fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+        self(to)
     }
 }
 
 macro_rules! write_unsized {
     ($t: ty) => {
         impl WritableText for $t {
-            fn write_as_text(self, to: &mut Vec<u8>) {
+            fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
                 let mut n = self;
-                let mut n2 = n;
-                let mut num_digits = 0;
+                let mut n2 = n / 10;
+                // zero is written as a single digit
+                let mut num_digits = 1;
                 while n2 > 0 {
                     n2 /= 10;
                     num_digits += 1;
                 }
-                let len = num_digits;
-                to.reserve(len);
-                let ptr = to.as_mut_ptr().cast::<u8>();
+                to.try_reserve(num_digits)?;
+                let ptr = to.spare_ptr();
                 let old_len = to.len();
-                let mut i = len - 1;
-                loop {
-                    unsafe { ptr.add(old_len + i).write((n % 10) as u8 + b'0') }
+                for i in (0..num_digits).rev() {
+                    unsafe { ptr.add(i).write((n % 10) as u8 + b'0') }
                     n /= 10;
-
-                    if n == 0 {
-                        break;
-                    } else {
-                        i -= 1;
-                    }
                 }
 
-                #[allow(clippy::uninit_vec)]
                 unsafe {
-                    to.set_len(old_len + (len - i));
+                    to.set_len(old_len + num_digits);
                 }
+                Ok(())
             }
         }
     };
@@ -136,46 +204,28 @@ macro_rules! write_unsized {
 macro_rules! write_sized {
     ($t: ty) => {
         impl WritableText for $t {
-            fn write_as_text(self, to: &mut Vec<u8>) {
-                let neg = self < 0;
-                let mut n = if neg {
-                    match self.checked_abs() {
-                        Some(n) => n,
-                        None => <$t>::MAX / 2 + 1,
-                    }
-                } else {
-                    self
-                };
-                let mut n2 = n;
-                let mut num_digits = 0;
-                while n2 > 0 {
-                    n2 /= 10;
-                    num_digits += 1;
-                }
-                let len = if neg { num_digits + 1 } else { num_digits };
-                to.reserve(len);
-                let ptr = to.as_mut_ptr().cast::<u8>();
-                let old_len = to.len();
-                let mut i = len - 1;
-                loop {
-                    unsafe { ptr.add(old_len + i).write((n % 10) as u8 + b'0') }
-                    n /= 10;
-
-                    if n == 0 {
-                        break;
-                    } else {
-                        i -= 1;
-                    }
-                }
-
-                if neg {
-                    i -= 1;
-                    unsafe { ptr.add(old_len + i).write(b'-') }
+            fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+                if self < 0 {
+                    to.try_push(b'-')?;
                 }
+                // the unsigned absolute value can represent the absolute value of MIN
+                self.unsigned_abs().write_as_text(to)
+            }
+        }
+    };
+}
 
-                #[allow(clippy::uninit_vec)]
-                unsafe {
-                    to.set_len(old_len + (len - i));
+/// Floats are written in the shortest form that parses back to the same value. Infinity and NaN are written the same way as javascript writes them.
+macro_rules! write_float {
+    ($t: ty) => {
+        impl WritableText for $t {
+            fn write_as_text(self, to: &mut impl Buffer) -> Result<(), BufferFull> {
+                if self.is_nan() {
+                    "NaN".write_as_text(to)
+                } else if self.is_infinite() {
+                    if self > 0. { "Infinity" } else { "-Infinity" }.write_as_text(to)
+                } else {
+                    DisplayText(self).write_as_text(to)
                 }
             }
         }
@@ -195,3 +245,107 @@ write_sized!(i32);
 write_sized!(i64);
 write_sized!(i128);
 write_sized!(isize);
+
+write_float!(f32);
+write_float!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec::Vec};
+    use buffer::SliceBuffer;
+
+    fn text(value: impl WritableText) -> Vec<u8> {
+        let mut buf = Vec::new();
+        value.write_as_text(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        assert_eq!(text(0u8), b"0");
+        assert_eq!(text(7u16), b"7");
+        assert_eq!(text(10u32), b"10");
+        assert_eq!(text(u8::MAX), b"255");
+        assert_eq!(text(u64::MAX), b"18446744073709551615");
+        assert_eq!(text(u128::MAX), b"340282366920938463463374607431768211455");
+        assert_eq!(text(usize::MAX), usize::MAX.to_string().as_bytes());
+    }
+
+    #[test]
+    fn signed_integers() {
+        assert_eq!(text(0i32), b"0");
+        assert_eq!(text(-1i8), b"-1");
+        assert_eq!(text(i8::MIN), b"-128");
+        assert_eq!(text(i16::MIN), b"-32768");
+        assert_eq!(text(i32::MIN), b"-2147483648");
+        assert_eq!(text(i64::MIN), b"-9223372036854775808");
+        assert_eq!(text(i128::MIN), b"-170141183460469231731687303715884105728");
+        assert_eq!(text(i64::MAX), b"9223372036854775807");
+        assert_eq!(text(isize::MIN), isize::MIN.to_string().as_bytes());
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(text(0f32), b"0");
+        assert_eq!(text(-0f64), b"-0");
+        assert_eq!(text(1.5f32), b"1.5");
+        assert_eq!(text(-0.1f64), b"-0.1");
+        assert_eq!(text(0.1f32), b"0.1");
+        assert_eq!(text(1e21f64), b"1000000000000000000000");
+        assert_eq!(text(f32::NAN), b"NaN");
+        assert_eq!(text(f64::NAN), b"NaN");
+        assert_eq!(text(f32::INFINITY), b"Infinity");
+        assert_eq!(text(f64::NEG_INFINITY), b"-Infinity");
+    }
+
+    #[test]
+    fn chars_and_bools() {
+        assert_eq!(text('a'), b"a");
+        assert_eq!(text('é'), "é".as_bytes());
+        assert_eq!(text('€'), [0xE2, 0x82, 0xAC]);
+        assert_eq!(text('🦀'), [0xF0, 0x9F, 0xA6, 0x80]);
+        assert_eq!(text(true), b"true");
+        assert_eq!(text(false), b"false");
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(text(""), b"");
+        assert_eq!(text("héllo"), "héllo".as_bytes());
+        assert_eq!(text(String::from("owned")), b"owned");
+        let borrowed = String::from("borrowed");
+        assert_eq!(text(&borrowed), b"borrowed");
+        assert_eq!(text(Cow::Borrowed("cow")), b"cow");
+        assert_eq!(text(Cow::<str>::Owned(String::from("moo"))), b"moo");
+    }
+
+    #[test]
+    fn formatted_text() {
+        assert_eq!(text(format_args!("{}-{:02}", "a", 7)), b"a-07");
+        assert_eq!(text(DisplayText(-12)), b"-12");
+        assert_eq!(text(DisplayText('ß')), "ß".as_bytes());
+    }
+
+    #[test]
+    fn closures_write_into_the_buffer() {
+        assert_eq!(
+            text(|to: &mut dyn Buffer| {
+                to.try_extend_from_slice(b"ab")?;
+                to.try_push(b'c')
+            }),
+            b"abc"
+        );
+    }
+
+    #[test]
+    fn text_that_does_not_fit_is_an_error() {
+        let mut buf = [0; 3];
+        let mut to = SliceBuffer::new(&mut buf);
+        assert!(1234u32.write_as_text(&mut to).is_err());
+        assert!("abcd".write_as_text(&mut to).is_err());
+        assert!(DisplayText(1234).write_as_text(&mut to).is_err());
+        assert!(123u32.write_as_text(&mut to).is_ok());
+        assert_eq!(to.as_slice(), b"123");
+    }
+}
@@ -0,0 +1,108 @@
+//! Namespaces of elements and attributes.
+//!
+//! Well-known namespaces and namespaces registered with [`CustomNames::namespace`](crate::custom::CustomNames::namespace) are encoded with a single byte. Any other namespace is written into the string buffer every time it is used.
+
+#![allow(non_camel_case_types)]
+
+use crate::{
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+};
+
+/// The byte that marks a namespace that is encoded as a string
+pub(crate) const STR_NAMESPACE: u8 = 255;
+
+name_table! {
+    /// Namespaces that the interpreter knows without registering them
+    pub enum KnownNamespace;
+    /// The error returned when parsing a uri that is not a [`KnownNamespace`]
+    pub struct ParseNamespaceError("not a well-known namespace");
+    svg = "http://www.w3.org/2000/svg",
+    mathml = "http://www.w3.org/1998/Math/MathML",
+    xlink = "http://www.w3.org/1999/xlink",
+    xml = "http://www.w3.org/XML/1998/namespace",
+    xmlns = "http://www.w3.org/2000/xmlns/",
+}
+
+/// An application specific namespace that is encoded with a single byte like a [`KnownNamespace`]. Custom namespaces are created with [`CustomNames::namespace`](crate::custom::CustomNames::namespace).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomNamespace(pub(crate) u8);
+
+impl CustomNamespace {
+    /// The byte the namespace is encoded as
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+}
+
+/// The namespace of an element or attribute
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Namespace<'a> {
+    /// A well-known namespace encoded with a single byte
+    Known(KnownNamespace),
+    /// A registered namespace encoded with a single byte
+    Custom(CustomNamespace),
+    /// Any other namespace encoded as a string
+    Str(&'a str),
+}
+
+impl<'a> Namespace<'a> {
+    /// The number of bytes the namespace takes up in the message
+    pub(crate) const fn size(&self) -> usize {
+        match self {
+            Namespace::Known(_) | Namespace::Custom(_) => 1,
+            Namespace::Str(_) => 1 + 2,
+        }
+    }
+
+    pub(crate) fn encode<B: Buffer>(&self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        match self {
+            Namespace::Known(ns) => v.msg.try_push(*ns as u8),
+            Namespace::Custom(ns) => v.msg.try_push(ns.0),
+            Namespace::Str(ns) => {
+                v.msg.try_push(STR_NAMESPACE)?;
+                v.encode_cachable_str(*ns)
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// This is only safe if the batch is preallocated to the correct size
+    pub(crate) unsafe fn encode_prealloc<B: Buffer>(
+        &self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
+        match self {
+            Namespace::Known(ns) => v.encode_u8_prealloc(*ns as u8),
+            Namespace::Custom(ns) => v.encode_u8_prealloc(ns.0),
+            Namespace::Str(ns) => {
+                v.encode_u8_prealloc(STR_NAMESPACE);
+                return v.encode_str_prealloc(*ns);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<KnownNamespace> for Namespace<'a> {
+    fn from(ns: KnownNamespace) -> Self {
+        Namespace::Known(ns)
+    }
+}
+
+impl<'a> From<CustomNamespace> for Namespace<'a> {
+    fn from(ns: CustomNamespace) -> Self {
+        Namespace::Custom(ns)
+    }
+}
+
+/// Well-known namespace uris are turned into a [`KnownNamespace`]. Any other uri is encoded as a string, use [`CustomNames::namespace`](crate::custom::CustomNames::namespace) to encode it with a single byte.
+impl<'a> From<&'a str> for Namespace<'a> {
+    fn from(ns: &'a str) -> Self {
+        match ns.parse() {
+            Ok(ns) => Namespace::Known(ns),
+            Err(_) => Namespace::Str(ns),
+        }
+    }
+}
@@ -0,0 +1,1215 @@
+//! A peephole optimizer for encoded batches.
+//!
+//! The optimizer decodes a batch, removes or rewrites operations that cannot change the resulting DOM, and encodes the remaining operations into a new batch.
+//! The following sequences are rewritten:
+//! - Cursor moves ([`Batch::set_last_node`], [`Batch::first_child`], [`Batch::next_sibling`], [`Batch::parent_node`], [`Batch::navigate`] and the other navigation methods) whose result is never read before the last node is replaced are removed, as are [`Batch::set_last_node`] calls to the node that is already the last node.
+//! - [`Batch::set_text`] calls on a node id that are overwritten by a later [`Batch::set_text`] on the same id are removed.
+//! - [`Batch::set_attribute`] calls on a node id that are overwritten or removed by a later operation on the same attribute of the same id are removed.
+//! - [`Batch::create_element`] followed by [`Batch::set_attribute`] calls on the new element are merged into a single [`Batch::build_full_element`] if it makes the batch smaller.
+//!
+//! Nodes that are removed from the DOM can be inserted again later, so writes to removed nodes are kept.
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    batch::{Batch, FinalizedBatch, StringEncoding},
+    buffer::{Buffer, BufferFull},
+    extended::EXTENDED,
+    namespace::STR_NAMESPACE,
+    prefixed::{ARIA_PREFIX, DATA_PREFIX},
+    style::STR_STYLE,
+    MaybeId, NodeId, Op,
+};
+
+impl<B: Buffer> Batch<B> {
+    /// Rewrites the batch into an equivalent batch with redundant operations removed.
+    ///
+    /// Running the optimized batch results in the same DOM as running the original batch. The optimized batch is encoded into the buffers of the original batch. If the original batch is full, the optimized batch is full as well because the operations that did not fit are still missing.
+    pub fn optimize(self) -> Batch<B> {
+        let full = self.full;
+        let finalized = self.finalize();
+        let mut optimized = Batch::default();
+        optimized.set_string_encoding(finalized.string_encoding);
+        optimize_into(
+            finalized.msg.as_slice(),
+            finalized.str.as_slice(),
+            &mut optimized,
+        );
+        // the optimized batch is never larger than the original batch, so it always fits in the original buffers
+        let mut batch = Batch::with_buffers(finalized.msg, finalized.str);
+        batch.set_string_encoding(finalized.string_encoding);
+        batch.append(optimized);
+        batch.full = full;
+        batch
+    }
+}
+
+impl<B: Buffer> FinalizedBatch<B> {
+    /// Rewrites the batch into an equivalent batch with redundant operations removed.
+    ///
+    /// Running the optimized batch results in the same DOM as running the original batch.
+    pub fn optimize(&self) -> FinalizedBatch {
+        let mut optimized = Batch::default();
+        optimized.set_string_encoding(self.string_encoding);
+        optimize_into(self.msg.as_slice(), self.str.as_slice(), &mut optimized);
+        optimized.finalize()
+    }
+}
+
+/// Decodes a finalized batch, optimizes it, and encodes every operation except the final stop into `out`. The strings are in the string encoding of `out`.
+fn optimize_into(msg: &[u8], str: &[u8], out: &mut Batch) {
+    let encoding = out.string_encoding();
+    let mut ops = decode(msg, str, encoding);
+    merge_build_full_element(&mut ops, msg);
+    remove_overwritten_writes(&mut ops, encoding);
+    remove_redundant_set_last_node(&mut ops);
+    remove_dead_cursor_moves(&mut ops);
+    for op in &ops {
+        out.encode_with(|out| op.encode(msg, str, out));
+    }
+}
+
+/// The first of up to three bools packed into the op byte
+const FIRST_BOOL: u8 = 1 << 5;
+/// The second of up to three bools packed into the op byte
+const SECOND_BOOL: u8 = 1 << 6;
+/// The third of up to three bools packed into the op byte
+const THIRD_BOOL: u8 = 1 << 7;
+
+/// An attribute as it is encoded in the batch. Attributes are only equal if they are encoded the same way.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum AttrKey<'a> {
+    Attribute(u8),
+    Extended(u8),
+    InNamespace(u8, NsKey<'a>),
+    Prefixed(u8, &'a [u8]),
+    Str(&'a [u8]),
+    InNamespaceStr(&'a [u8], NsKey<'a>),
+}
+
+/// A namespace as it is encoded in the batch
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum NsKey<'a> {
+    Code(u8),
+    Str(&'a [u8]),
+}
+
+/// What a decoded operation does
+enum Kind<'a> {
+    /// First child, next sibling or parent node
+    Navigate,
+    SetLastNode(NodeId),
+    StoreWithId(NodeId),
+    /// Restores the last node from the cursor stack
+    PopCursor,
+    /// Create element, create text node, or build full element
+    Create {
+        ids: Vec<NodeId>,
+    },
+    CreateElement {
+        id: Option<NodeId>,
+        /// The range of the encoded element in the message
+        tag: Range<usize>,
+    },
+    SetText(MaybeId),
+    /// Append, insert or delete text which depends on the current text of the target
+    EditText(MaybeId),
+    SetAttribute {
+        target: MaybeId,
+        attr: AttrKey<'a>,
+        /// The discriminant that must be prepended to `payload` to encode the attribute in a [`Op::BuildFullElement`], if the attribute can be encoded there
+        discriminant: Option<Option<u8>>,
+        /// The range of the encoded attribute and value in the message
+        payload: Range<usize>,
+    },
+    RemoveAttribute {
+        target: MaybeId,
+        attr: AttrKey<'a>,
+    },
+    SetStyle(MaybeId),
+    /// Reads or changes the attributes of the target in a way that is not tracked
+    ChangeAttributes(MaybeId),
+    /// Changes the attributes of many nodes in a way that is not tracked
+    ChangeAttributesOf(Vec<NodeId>),
+    /// Clones a node and stores the clone or its descendants with ids
+    CloneNode {
+        src: MaybeId,
+        ids: Vec<NodeId>,
+    },
+    /// Stores the nodes at paths from the target with ids
+    StorePaths {
+        root: MaybeId,
+        ids: Vec<NodeId>,
+    },
+    /// Insert before, insert after, or replace with
+    Insert {
+        reads_cursor: bool,
+    },
+    Other {
+        reads_cursor: bool,
+    },
+    NoOp,
+    Stop,
+}
+
+/// A decoded operation
+struct DecodedOp<'a> {
+    /// The op with the bools packed into it
+    op: u8,
+    /// The range of the data for the op in the message
+    data: Range<usize>,
+    /// The range of the strings for the op in the string buffer
+    strs: Range<usize>,
+    kind: Kind<'a>,
+    /// Bytes that replace the data in the message. Used for ops created by the optimizer
+    rewritten: Option<Vec<u8>>,
+}
+
+impl DecodedOp<'_> {
+    fn reads_cursor(&self) -> bool {
+        match &self.kind {
+            Kind::Navigate | Kind::StoreWithId(_) | Kind::Stop => true,
+            Kind::SetLastNode(_)
+            | Kind::Create { .. }
+            | Kind::CreateElement { .. }
+            | Kind::ChangeAttributesOf(_)
+            | Kind::PopCursor
+            | Kind::NoOp => false,
+            Kind::SetText(target)
+            | Kind::EditText(target)
+            | Kind::SetAttribute { target, .. }
+            | Kind::RemoveAttribute { target, .. }
+            | Kind::SetStyle(target)
+            | Kind::ChangeAttributes(target)
+            | Kind::StorePaths { root: target, .. }
+            | Kind::CloneNode { src: target, .. } => *target == MaybeId::LastNode,
+            Kind::Insert { reads_cursor } | Kind::Other { reads_cursor } => *reads_cursor,
+        }
+    }
+
+    fn writes_cursor(&self) -> bool {
+        matches!(
+            self.kind,
+            Kind::Navigate
+                | Kind::SetLastNode(_)
+                | Kind::PopCursor
+                | Kind::Create { .. }
+                | Kind::CreateElement { .. }
+                | Kind::CloneNode { .. }
+        )
+    }
+
+    /// If the op only moves the last node without changing the DOM
+    fn is_cursor_move(&self) -> bool {
+        matches!(self.kind, Kind::Navigate | Kind::SetLastNode(_))
+    }
+
+    /// The ids this op stores a new node under
+    fn assigned_ids(&self) -> &[NodeId] {
+        match &self.kind {
+            Kind::StoreWithId(id) => core::slice::from_ref(id),
+            Kind::Create { ids } | Kind::StorePaths { ids, .. } | Kind::CloneNode { ids, .. } => {
+                ids
+            }
+            Kind::CreateElement { id: Some(id), .. } => core::slice::from_ref(id),
+            _ => &[],
+        }
+    }
+
+    fn encode(
+        &self,
+        msg: &[u8],
+        str: &[u8],
+        out: &mut Batch<impl Buffer>,
+    ) -> Result<(), BufferFull> {
+        if matches!(self.kind, Kind::NoOp | Kind::Stop) {
+            return Ok(());
+        }
+        out.encode_raw_op(self.op)?;
+        match &self.rewritten {
+            Some(data) => out.msg.try_extend_from_slice(data)?,
+            None => out.msg.try_extend_from_slice(&msg[self.data.clone()])?,
+        }
+        out.str_buf.try_extend_from_slice(&str[self.strs.clone()])
+    }
+}
+
+/// Reads a finalized batch
+struct Decoder<'a> {
+    msg: &'a [u8],
+    str: &'a [u8],
+    pos: usize,
+    str_pos: usize,
+    encoding: StringEncoding,
+    /// The positions in the message of the lengths of the strings that were read
+    str_lens: Vec<usize>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(msg: &'a [u8], str: &'a [u8], encoding: StringEncoding) -> Self {
+        Self {
+            msg,
+            str,
+            pos: 0,
+            str_pos: 0,
+            encoding,
+            str_lens: Vec::new(),
+        }
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.pos += 1;
+        self.msg[self.pos - 1]
+    }
+
+    fn u16(&mut self) -> u16 {
+        self.pos += 2;
+        u16::from_le_bytes([self.msg[self.pos - 2], self.msg[self.pos - 1]])
+    }
+
+    fn u32(&mut self) -> u32 {
+        self.pos += 4;
+        u32::from_le_bytes(self.msg[self.pos - 4..self.pos].try_into().unwrap())
+    }
+
+    fn id(&mut self) -> NodeId {
+        NodeId(self.u32())
+    }
+
+    fn maybe_id(&mut self, is_node: bool) -> MaybeId {
+        if is_node {
+            MaybeId::Node(self.id())
+        } else {
+            MaybeId::LastNode
+        }
+    }
+
+    fn optional_id(&mut self, is_some: bool) -> Option<NodeId> {
+        is_some.then(|| self.id())
+    }
+
+    fn str(&mut self) -> &'a [u8] {
+        self.str_lens.push(self.pos);
+        let len = match self.encoding {
+            StringEncoding::Utf8 => self.u16() as usize,
+            StringEncoding::Utf16 => self.u16() as usize * 2,
+        };
+        self.str_pos += len;
+        &self.str[self.str_pos - len..self.str_pos]
+    }
+
+    /// Reads text that is either in the string buffer or a reference to a static string in memory
+    fn text(&mut self, is_static: bool) {
+        if is_static {
+            self.u32();
+            self.u32();
+        } else {
+            self.str();
+        }
+    }
+
+    fn maybe_id_u8_discriminant(&mut self) -> MaybeId {
+        let is_node = self.u8() != 0;
+        self.maybe_id(is_node)
+    }
+
+    /// Reads a namespace encoded with [`crate::Namespace`]
+    fn namespace(&mut self) -> NsKey<'a> {
+        match self.u8() {
+            STR_NAMESPACE => NsKey::Str(self.str()),
+            code => NsKey::Code(code),
+        }
+    }
+
+    /// Reads an element encoded with [`crate::IntoElement::encode`]
+    fn element(&mut self) {
+        match self.u8() {
+            255 => {
+                self.u8();
+                self.namespace();
+            }
+            254 => {
+                self.str();
+            }
+            253 => {
+                self.str();
+                self.namespace();
+            }
+            EXTENDED => {
+                self.u8();
+            }
+            _ => {}
+        }
+    }
+
+    /// Reads an attribute encoded with [`crate::IntoAttribue::encode_u8_discriminant_prealloc`]
+    fn attribute_u8_discriminant(&mut self) {
+        match self.u8() {
+            255 => {
+                self.u8();
+                self.namespace();
+            }
+            254 => {
+                self.str();
+            }
+            253 => {
+                self.str();
+                self.namespace();
+            }
+            EXTENDED => {
+                self.u8();
+            }
+            DATA_PREFIX | ARIA_PREFIX => {
+                self.str();
+            }
+            _ => {}
+        }
+    }
+
+    /// Reads an attribute encoded with [`crate::IntoAttribue::encode`]
+    fn attribute(&mut self, op: u8) -> AttrKey<'a> {
+        match (op & SECOND_BOOL != 0, op & THIRD_BOOL != 0) {
+            (false, false) => match self.u8() {
+                EXTENDED => AttrKey::Extended(self.u8()),
+                prefix @ (DATA_PREFIX | ARIA_PREFIX) => AttrKey::Prefixed(prefix, self.str()),
+                attr => AttrKey::Attribute(attr),
+            },
+            (false, true) => {
+                let attr = self.u8();
+                AttrKey::InNamespace(attr, self.namespace())
+            }
+            (true, false) => AttrKey::Str(self.str()),
+            (true, true) => {
+                let attr = self.str();
+                AttrKey::InNamespaceStr(attr, self.namespace())
+            }
+        }
+    }
+
+    /// Reads a style property encoded with [`crate::IntoStyle::encode`]
+    fn style(&mut self, op: u8) {
+        if op & SECOND_BOOL != 0 {
+            self.str();
+        } else {
+            self.u8();
+        }
+    }
+
+    /// Reads a node encoded with [`crate::NodeBuilder::encode`] and collects the ids it assigns
+    fn node(&mut self, ids: &mut Vec<NodeId>) {
+        let flags = self.u8();
+        if flags & 1 != 0 {
+            ids.push(self.id());
+        }
+        if flags & 2 != 0 {
+            self.str();
+        } else {
+            self.element();
+            let attrs = self.u8();
+            let children = self.u8();
+            for _ in 0..attrs {
+                self.attribute_u8_discriminant();
+                self.str();
+            }
+            for _ in 0..children {
+                self.node(ids);
+            }
+        }
+    }
+
+    /// Reads a path of child indices
+    fn path(&mut self) {
+        self.pos += self.u8() as usize;
+    }
+
+    /// Reads a list of paths and returns the ids the nodes are stored with
+    fn paths(&mut self) -> Vec<NodeId> {
+        (0..self.u16())
+            .map(|_| {
+                self.path();
+                self.id()
+            })
+            .collect()
+    }
+
+    /// Reads the data for the nodes of an insert or replace operation
+    fn insert(&mut self, op: u8) -> bool {
+        let mut reads_cursor = self.maybe_id(op & SECOND_BOOL != 0) == MaybeId::LastNode;
+        if op & FIRST_BOOL != 0 && op & THIRD_BOOL != 0 {
+            // the nodes are on the node stack
+            self.u16();
+        } else if op & FIRST_BOOL != 0 {
+            for _ in 0..self.u8() {
+                reads_cursor |= self.maybe_id_u8_discriminant() == MaybeId::LastNode;
+            }
+        } else {
+            reads_cursor |= self.maybe_id(op & THIRD_BOOL != 0) == MaybeId::LastNode;
+        }
+        reads_cursor
+    }
+
+    fn op(&mut self, op: u8) -> Kind<'a> {
+        let first = op & FIRST_BOOL != 0;
+        let second = op & SECOND_BOOL != 0;
+        match op & 0x1F {
+            x if x == Op::FirstChild as u8 => {
+                // the second bool marks the nth child
+                if second {
+                    self.u32();
+                }
+                Kind::Navigate
+            }
+            x if x == Op::NextSibling as u8 || x == Op::ParentNode as u8 => Kind::Navigate,
+            // popping the cursor replaces the last node with a node that is not tracked
+            x if x == Op::Cursor as u8 && first => Kind::PopCursor,
+            x if x == Op::Cursor as u8 => Kind::Other { reads_cursor: true },
+            x if x == Op::Navigate as u8 => {
+                if first {
+                    let root = self.maybe_id(second);
+                    let ids = self.paths();
+                    Kind::StorePaths { root, ids }
+                } else {
+                    self.path();
+                    Kind::Navigate
+                }
+            }
+            x if x == Op::StoreWithId as u8 => Kind::StoreWithId(self.id()),
+            x if x == Op::SetLastNode as u8 => Kind::SetLastNode(self.id()),
+            x if x == Op::Stop as u8 => Kind::Stop,
+            x if x == Op::BuildFullElement as u8 => {
+                let mut ids = Vec::new();
+                self.node(&mut ids);
+                Kind::Create { ids }
+            }
+            x if x == Op::AppendChildren as u8 => {
+                let root = self.maybe_id(first);
+                // the third bool marks children from the node stack
+                let child = if op & THIRD_BOOL != 0 {
+                    self.u16();
+                    None
+                } else {
+                    Some(self.maybe_id(second))
+                };
+                Kind::Other {
+                    reads_cursor: root == MaybeId::LastNode || child == Some(MaybeId::LastNode),
+                }
+            }
+            x if x == Op::PushRoot as u8 => Kind::Other {
+                reads_cursor: self.maybe_id(first) == MaybeId::LastNode,
+            },
+            x if x == Op::ReplaceWith as u8
+                || x == Op::InsertAfter as u8
+                || x == Op::InsertBefore as u8 =>
+            {
+                Kind::Insert {
+                    reads_cursor: self.insert(op),
+                }
+            }
+            x if x == Op::Remove as u8 => Kind::Other {
+                reads_cursor: self.maybe_id(first) == MaybeId::LastNode,
+            },
+            x if x == Op::CreateTextNode as u8 => {
+                self.text(second);
+                Kind::Create {
+                    ids: self.optional_id(first).into_iter().collect(),
+                }
+            }
+            x if x == Op::CreateElement as u8 => {
+                let start = self.pos;
+                self.element();
+                let tag = start..self.pos;
+                Kind::CreateElement {
+                    tag,
+                    id: self.optional_id(first),
+                }
+            }
+            x if x == Op::SetText as u8 => {
+                let target = self.maybe_id(first);
+                self.text(second);
+                Kind::SetText(target)
+            }
+            x if x == Op::SetAttribute as u8 => {
+                let target = self.maybe_id(first);
+                let start = self.pos;
+                let attr = self.attribute(op);
+                self.str();
+                let discriminant = match attr {
+                    AttrKey::Attribute(_) | AttrKey::Extended(_) | AttrKey::Prefixed(..) => {
+                        Some(None)
+                    }
+                    AttrKey::InNamespace(..) => Some(Some(255)),
+                    AttrKey::Str(_) => Some(Some(254)),
+                    AttrKey::InNamespaceStr(..) => Some(Some(253)),
+                };
+                Kind::SetAttribute {
+                    target,
+                    attr,
+                    discriminant,
+                    payload: start..self.pos,
+                }
+            }
+            x if x == Op::RemoveAttribute as u8 => {
+                let target = self.maybe_id(first);
+                let attr = self.attribute(op);
+                Kind::RemoveAttribute { target, attr }
+            }
+            x if x == Op::SetStyle as u8 => {
+                let target = self.maybe_id(first);
+                self.style(op);
+                self.str();
+                Kind::SetStyle(target)
+            }
+            x if x == Op::SetStyles as u8 => {
+                let target = self.maybe_id(first);
+                for _ in 0..self.u8() {
+                    if self.u8() == STR_STYLE {
+                        self.str();
+                    }
+                    self.str();
+                }
+                Kind::SetStyle(target)
+            }
+            x if x == Op::SetCssText as u8 => {
+                let target = self.maybe_id(first);
+                self.str();
+                Kind::SetStyle(target)
+            }
+            x if x == Op::RemoveStyle as u8 => {
+                let target = self.maybe_id(first);
+                self.style(op);
+                Kind::Other {
+                    reads_cursor: target == MaybeId::LastNode,
+                }
+            }
+            x if x == Op::CloneNode as u8 => {
+                let src = self.maybe_id(first);
+                let ids = self.optional_id(second).into_iter().collect();
+                Kind::CloneNode { src, ids }
+            }
+            x if x == Op::CloneNodeWithIds as u8 => {
+                let src = self.maybe_id(first);
+                let ids = self.paths();
+                Kind::CloneNode { src, ids }
+            }
+            x if x == Op::NoOp as u8 => Kind::NoOp,
+            x if x == Op::SetAttributes as u8 => {
+                let target = self.maybe_id(first);
+                for _ in 0..self.u8() {
+                    self.attribute_u8_discriminant();
+                    self.str();
+                }
+                Kind::ChangeAttributes(target)
+            }
+            x if x == Op::SetAttributeMany as u8 => {
+                self.attribute_u8_discriminant();
+                let len = self.u16();
+                let ids = if first {
+                    self.str();
+                    (0..len).map(|_| self.id()).collect()
+                } else {
+                    (0..len)
+                        .map(|_| {
+                            let id = self.id();
+                            self.str();
+                            id
+                        })
+                        .collect()
+                };
+                Kind::ChangeAttributesOf(ids)
+            }
+            x if x == Op::Dataset as u8 => {
+                let target = self.maybe_id(first);
+                self.str();
+                if !second {
+                    self.str();
+                }
+                Kind::ChangeAttributes(target)
+            }
+            x if x == Op::EditText as u8 => {
+                let target = self.maybe_id(first);
+                let third = op & THIRD_BOOL != 0;
+                if second || third {
+                    self.u32();
+                }
+                if third {
+                    self.u32();
+                } else {
+                    self.str();
+                }
+                Kind::EditText(target)
+            }
+            x if x == Op::ToggleAttribute as u8 => {
+                let target = self.maybe_id(first);
+                self.attribute_u8_discriminant();
+                Kind::ChangeAttributes(target)
+            }
+            x => panic!("unknown op {x}"),
+        }
+    }
+}
+
+/// Decodes every operation in a finalized batch up to and including the final stop
+fn decode<'a>(msg: &'a [u8], str: &'a [u8], encoding: StringEncoding) -> Vec<DecodedOp<'a>> {
+    decode_with(&mut Decoder::new(msg, str, encoding))
+}
+
+/// The positions in a finalized batch of the lengths of every string in the string buffer, in the order the strings are in the string buffer
+pub(crate) fn string_lengths(msg: &[u8], str: &[u8], encoding: StringEncoding) -> Vec<usize> {
+    let mut decoder = Decoder::new(msg, str, encoding);
+    decode_with(&mut decoder);
+    decoder.str_lens
+}
+
+fn decode_with<'a>(decoder: &mut Decoder<'a>) -> Vec<DecodedOp<'a>> {
+    let msg = decoder.msg;
+    let mut ops = Vec::new();
+    loop {
+        // ops are packed in groups of four before the data for those ops
+        let op_batch = decoder.pos;
+        decoder.pos += 4;
+        for op in &msg[op_batch..op_batch + 4] {
+            let data_start = decoder.pos;
+            let str_start = decoder.str_pos;
+            let kind = decoder.op(*op);
+            let stop = matches!(kind, Kind::Stop);
+            ops.push(DecodedOp {
+                op: *op,
+                data: data_start..decoder.pos,
+                strs: str_start..decoder.str_pos,
+                kind,
+                rewritten: None,
+            });
+            if stop {
+                return ops;
+            }
+        }
+    }
+}
+
+/// Merges create element followed by set attribute calls on the new element into a single build full element
+fn merge_build_full_element(ops: &mut Vec<DecodedOp>, msg: &[u8]) {
+    let mut i = 0;
+    while i < ops.len() {
+        if let Kind::CreateElement { id, tag } = &ops[i].kind {
+            let (id, tag) = (*id, tag.clone());
+            let attrs: Vec<_> = ops[i + 1..]
+                .iter()
+                .take_while(|op| match &op.kind {
+                    Kind::SetAttribute {
+                        target,
+                        discriminant: Some(_),
+                        ..
+                    } => match target {
+                        MaybeId::LastNode => true,
+                        MaybeId::Node(target) => Some(*target) == id,
+                    },
+                    _ => false,
+                })
+                .take(u8::MAX as usize)
+                .collect();
+            let old_size: usize = ops[i..=i + attrs.len()]
+                .iter()
+                .map(|op| 1 + op.data.len())
+                .sum();
+
+            let mut data = Vec::new();
+            match id {
+                Some(id) => {
+                    data.push(1);
+                    data.extend_from_slice(&id.0.to_le_bytes());
+                }
+                None => data.push(0),
+            }
+            data.extend_from_slice(&msg[tag]);
+            data.push(attrs.len() as u8);
+            data.push(0);
+            for attr in &attrs {
+                if let Kind::SetAttribute {
+                    discriminant: Some(discriminant),
+                    payload,
+                    ..
+                } = &attr.kind
+                {
+                    data.extend(discriminant);
+                    data.extend_from_slice(&msg[payload.clone()]);
+                }
+            }
+
+            if !attrs.is_empty() && data.len() < old_size {
+                let merged = attrs.len();
+                let strs = ops[i].strs.start..ops[i + merged].strs.end;
+                ops[i] = DecodedOp {
+                    op: Op::BuildFullElement as u8,
+                    data: 0..0,
+                    strs,
+                    kind: Kind::Create {
+                        ids: id.into_iter().collect(),
+                    },
+                    rewritten: Some(data),
+                };
+                ops.drain(i + 1..=i + merged);
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Removes set text and set attribute calls that are overwritten before they can be observed
+fn remove_overwritten_writes(ops: &mut Vec<DecodedOp>, encoding: StringEncoding) {
+    // node ids that have their text set later in the batch
+    let mut text_set = BTreeSet::new();
+    // attributes that are set later in the batch. The bool is true if the attribute is removed which doesn't depend on the order of the attributes
+    let mut attrs_set: BTreeSet<(NodeId, AttrKey, bool)> = BTreeSet::new();
+    let mut keep = vec![true; ops.len()];
+
+    for (i, op) in ops.iter().enumerate().rev() {
+        for id in op.assigned_ids() {
+            text_set.remove(id);
+            attrs_set.retain(|(node, _, _)| node != id);
+        }
+        match &op.kind {
+            Kind::SetText(MaybeId::Node(id)) => keep[i] = text_set.insert(*id),
+            // deleting text from a text node that is too short throws, so the text before an edit must be kept
+            Kind::EditText(MaybeId::Node(id)) => {
+                text_set.remove(id);
+            }
+            Kind::EditText(MaybeId::LastNode) => text_set.clear(),
+            Kind::SetAttribute {
+                target: MaybeId::Node(id),
+                attr,
+                ..
+            } => {
+                if attrs_set.contains(&(*id, attr.clone(), false))
+                    || attrs_set.contains(&(*id, attr.clone(), true))
+                {
+                    keep[i] = false;
+                } else {
+                    // adding a different attribute between two sets of the same attribute changes the order of the attributes
+                    attrs_set.retain(|(node, a, removed)| node != id || a == attr || *removed);
+                    attrs_set.insert((*id, attr.clone(), false));
+                }
+            }
+            Kind::RemoveAttribute {
+                target: MaybeId::Node(id),
+                attr,
+            } => {
+                attrs_set.insert((*id, attr.clone(), true));
+            }
+            Kind::SetAttribute {
+                target: MaybeId::LastNode,
+                ..
+            }
+            | Kind::SetStyle(MaybeId::LastNode) => {
+                attrs_set.retain(|(_, _, removed)| *removed);
+            }
+            Kind::SetStyle(MaybeId::Node(id)) => {
+                // setting a style may add the style attribute
+                attrs_set.retain(|(node, a, removed)| {
+                    node != id || *removed || !attr_is(a, "style", encoding)
+                });
+            }
+            Kind::ChangeAttributes(MaybeId::Node(id)) => {
+                attrs_set.retain(|(node, _, _)| node != id);
+            }
+            Kind::ChangeAttributesOf(ids) => {
+                attrs_set.retain(|(node, _, _)| !ids.contains(node));
+            }
+            Kind::ChangeAttributes(MaybeId::LastNode) | Kind::CloneNode { .. } => {
+                text_set.clear();
+                attrs_set.clear();
+            }
+            // storing paths walks the current children of the root, which setting text replaces
+            Kind::Navigate | Kind::StorePaths { .. } | Kind::PopCursor | Kind::Insert { .. } => {
+                text_set.clear();
+            }
+            _ => {}
+        }
+    }
+
+    let mut keep = keep.into_iter();
+    ops.retain(|_| keep.next().unwrap());
+}
+
+/// Checks if an attribute key could be the attribute with the given name
+fn attr_is(attr: &AttrKey, name: &str, encoding: StringEncoding) -> bool {
+    match attr {
+        AttrKey::Str(s) | AttrKey::InNamespaceStr(s, _) => match encoding {
+            StringEncoding::Utf8 => *s == name.as_bytes(),
+            StringEncoding::Utf16 => s
+                .chunks(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .eq(name.encode_utf16()),
+        },
+        AttrKey::Prefixed(..) => false,
+        // we don't know the names of the attribute enum here, so any attribute could be the attribute
+        AttrKey::Attribute(_) | AttrKey::Extended(_) | AttrKey::InNamespace(..) => true,
+    }
+}
+
+/// Removes set last node calls that set the last node to the node that is already the last node
+fn remove_redundant_set_last_node(ops: &mut Vec<DecodedOp>) {
+    let mut last_node = None;
+    ops.retain(|op| {
+        match &op.kind {
+            Kind::SetLastNode(id) => {
+                if last_node == Some(*id) {
+                    return false;
+                }
+                last_node = Some(*id);
+            }
+            // the last node is now stored with the id
+            Kind::StoreWithId(id) => last_node = Some(*id),
+            _ => {
+                if op.writes_cursor() || last_node.is_some_and(|id| op.assigned_ids().contains(&id))
+                {
+                    last_node = None;
+                }
+            }
+        }
+        true
+    });
+}
+
+/// Removes cursor moves that are replaced before the last node is read
+fn remove_dead_cursor_moves(ops: &mut Vec<DecodedOp>) {
+    // the last node is kept between batches, so it is read after the batch ends
+    let mut live = true;
+    let mut keep = vec![true; ops.len()];
+    for (i, op) in ops.iter().enumerate().rev() {
+        if op.is_cursor_move() && !live {
+            keep[i] = false;
+        } else {
+            live = (live && !op.writes_cursor()) || op.reads_cursor();
+        }
+    }
+    let mut keep = keep.into_iter();
+    ops.retain(|_| keep.next().unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::SliceBuffer, element::ElementBuilder, Attribute, Element};
+
+    /// Optimizes the batch built by `before` and checks that it is encoded the same as the batch built by `after`
+    fn assert_optimizes_to(before: impl FnOnce(&mut Batch), after: impl FnOnce(&mut Batch)) {
+        let mut batch = Batch::default();
+        before(&mut batch);
+        let mut expected = Batch::default();
+        after(&mut expected);
+        let optimized = batch.finalize().optimize();
+        let expected = expected.finalize();
+        assert_eq!(optimized.msg, expected.msg);
+        assert_eq!(optimized.str, expected.str);
+    }
+
+    /// Checks that optimizing the batch does not change it
+    fn assert_unchanged(f: impl Fn(&mut Batch)) {
+        assert_optimizes_to(&f, &f);
+    }
+
+    #[test]
+    fn optimizes_into_the_original_buffers() {
+        let mut msg = [0; 64];
+        let mut str = [0; 64];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.set_text("a", MaybeId::Node(NodeId(1)));
+        batch.set_text("b", MaybeId::Node(NodeId(1)));
+        let optimized: Batch<SliceBuffer> = batch.optimize();
+        assert!(!optimized.is_full());
+        let optimized = optimized.finalize();
+
+        let mut expected = Batch::default();
+        expected.set_text("b", MaybeId::Node(NodeId(1)));
+        let expected = expected.finalize();
+        assert_eq!(optimized.msg.as_slice(), expected.msg);
+        assert_eq!(optimized.str.as_slice(), expected.str);
+    }
+
+    #[test]
+    fn optimizing_a_full_batch_keeps_it_full() {
+        let mut msg = [0; 16];
+        let mut str = [0; 2];
+        let mut batch = Batch::with_buffers(SliceBuffer::new(&mut msg), SliceBuffer::new(&mut str));
+        batch.set_text("a", MaybeId::Node(NodeId(1)));
+        batch.set_text("b", MaybeId::Node(NodeId(1)));
+        batch.set_text("cd", MaybeId::Node(NodeId(2)));
+        assert!(batch.is_full());
+
+        let optimized = batch.optimize();
+        assert!(optimized.is_full());
+        let optimized = optimized.finalize();
+
+        let mut expected = Batch::default();
+        expected.set_text("b", MaybeId::Node(NodeId(1)));
+        let expected = expected.finalize();
+        assert_eq!(optimized.msg.as_slice(), expected.msg);
+        assert_eq!(optimized.str.as_slice(), expected.str);
+    }
+
+    #[test]
+    fn merges_create_element_with_attributes() {
+        assert_optimizes_to(
+            |b| {
+                b.create_element(Element::div, Some(NodeId(1)));
+                b.set_attribute(Attribute::class, "a", MaybeId::LastNode);
+                b.set_attribute(Attribute::id, "b", MaybeId::Node(NodeId(1)));
+                b.set_attribute(Attribute::title, "c", MaybeId::LastNode);
+                b.set_attribute(Attribute::lang, "d", MaybeId::LastNode);
+            },
+            |b| {
+                b.build_full_element(
+                    ElementBuilder::new(Element::div.into())
+                        .id(NodeId(1))
+                        .attrs(&[
+                            (Attribute::class.any_attr_const(), "a"),
+                            (Attribute::id.any_attr_const(), "b"),
+                            (Attribute::title.any_attr_const(), "c"),
+                            (Attribute::lang.any_attr_const(), "d"),
+                        ]),
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn keeps_create_element_if_merging_is_larger() {
+        assert_unchanged(|b| {
+            b.create_element(Element::div, None);
+            b.set_attribute(Attribute::class, "a", MaybeId::LastNode);
+        });
+    }
+
+    #[test]
+    fn does_not_merge_attributes_of_other_nodes() {
+        assert_unchanged(|b| {
+            b.create_element(Element::div, Some(NodeId(1)));
+            b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(2)));
+            b.set_attribute(Attribute::id, "b", MaybeId::Node(NodeId(2)));
+            b.set_attribute(Attribute::title, "c", MaybeId::Node(NodeId(2)));
+            b.set_attribute(Attribute::lang, "d", MaybeId::Node(NodeId(2)));
+        });
+    }
+
+    #[test]
+    fn removes_overwritten_text() {
+        assert_optimizes_to(
+            |b| {
+                b.set_text("a", MaybeId::Node(NodeId(1)));
+                b.set_text("b", MaybeId::Node(NodeId(2)));
+                b.set_text("c", MaybeId::Node(NodeId(1)));
+            },
+            |b| {
+                b.set_text("b", MaybeId::Node(NodeId(2)));
+                b.set_text("c", MaybeId::Node(NodeId(1)));
+            },
+        );
+    }
+
+    #[test]
+    fn removes_overwritten_static_text() {
+        assert_optimizes_to(
+            |b| {
+                b.create_text_node_in_memory("a", Some(NodeId(1)));
+                b.set_text_in_memory("b", MaybeId::Node(NodeId(1)));
+                b.set_text("c", MaybeId::Node(NodeId(1)));
+                b.set_text("d", MaybeId::Node(NodeId(2)));
+                b.set_text_in_memory("e", MaybeId::Node(NodeId(2)));
+            },
+            |b| {
+                b.create_text_node_in_memory("a", Some(NodeId(1)));
+                b.set_text("c", MaybeId::Node(NodeId(1)));
+                b.set_text_in_memory("e", MaybeId::Node(NodeId(2)));
+            },
+        );
+    }
+
+    #[test]
+    fn keeps_text_before_an_edit() {
+        assert_unchanged(|b| {
+            b.set_text("abc", MaybeId::Node(NodeId(1)));
+            b.delete_text(1, 2, MaybeId::Node(NodeId(1)));
+            b.set_text("d", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn keeps_text_set_through_the_last_node() {
+        assert_unchanged(|b| {
+            b.set_last_node(NodeId(1));
+            b.set_text("a", MaybeId::LastNode);
+            b.set_text("b", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn keeps_text_before_storing_paths() {
+        assert_unchanged(|b| {
+            b.set_text("a", MaybeId::Node(NodeId(1)));
+            b.store_paths(MaybeId::Node(NodeId(1)), &[(&[0], NodeId(5))]);
+            b.set_text("b", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn removes_overwritten_attributes() {
+        assert_optimizes_to(
+            |b| {
+                b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(1)));
+                b.set_attribute(Attribute::class, "b", MaybeId::Node(NodeId(1)));
+                b.set_attribute(Attribute::id, "c", MaybeId::Node(NodeId(2)));
+                b.remove_attribute(Attribute::id, MaybeId::Node(NodeId(2)));
+            },
+            |b| {
+                b.set_attribute(Attribute::class, "b", MaybeId::Node(NodeId(1)));
+                b.remove_attribute(Attribute::id, MaybeId::Node(NodeId(2)));
+            },
+        );
+    }
+
+    #[test]
+    fn removes_overwritten_attributes_of_nodes_not_in_a_list() {
+        assert_optimizes_to(
+            |b| {
+                b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(1)));
+                b.set_attribute_many(Attribute::class, &[(NodeId(2), "b"), (NodeId(3), "c")]);
+                b.set_attribute_all(Attribute::class, "d", &[NodeId(4)]);
+                b.set_attribute(Attribute::class, "e", MaybeId::Node(NodeId(1)));
+            },
+            |b| {
+                b.set_attribute_many(Attribute::class, &[(NodeId(2), "b"), (NodeId(3), "c")]);
+                b.set_attribute_all(Attribute::class, "d", &[NodeId(4)]);
+                b.set_attribute(Attribute::class, "e", MaybeId::Node(NodeId(1)));
+            },
+        );
+    }
+
+    #[test]
+    fn keeps_attributes_of_nodes_in_a_list() {
+        assert_unchanged(|b| {
+            b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(1)));
+            b.set_attribute(Attribute::class, "b", MaybeId::Node(NodeId(2)));
+            b.set_attribute_many(Attribute::title, &[(NodeId(3), "c"), (NodeId(1), "d")]);
+            b.set_attribute_all(Attribute::title, "e", &[NodeId(2)]);
+            b.set_attribute(Attribute::class, "f", MaybeId::Node(NodeId(1)));
+            b.set_attribute(Attribute::class, "g", MaybeId::Node(NodeId(2)));
+        });
+    }
+
+    #[test]
+    fn keeps_attributes_that_change_the_attribute_order() {
+        assert_unchanged(|b| {
+            b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(1)));
+            b.set_attribute(Attribute::id, "b", MaybeId::Node(NodeId(1)));
+            b.set_attribute(Attribute::class, "c", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn keeps_the_style_attribute_before_a_style_change() {
+        assert_unchanged(|b| {
+            b.set_attribute("style", "color: red", MaybeId::Node(NodeId(1)));
+            b.set_style(crate::Style::color, "blue", MaybeId::Node(NodeId(1)));
+            b.set_attribute("style", "color: green", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn keeps_writes_to_ids_that_are_reassigned() {
+        assert_unchanged(|b| {
+            b.set_text("a", MaybeId::Node(NodeId(1)));
+            b.store_paths(MaybeId::Node(NodeId(0)), &[(&[0], NodeId(1))]);
+            b.set_text("b", MaybeId::Node(NodeId(1)));
+        });
+        assert_unchanged(|b| {
+            b.set_attribute(Attribute::class, "a", MaybeId::Node(NodeId(1)));
+            b.clone_node_with_ids(MaybeId::Node(NodeId(0)), &[(&[], NodeId(1))]);
+            b.set_attribute(Attribute::class, "b", MaybeId::Node(NodeId(1)));
+        });
+    }
+
+    #[test]
+    fn removes_redundant_set_last_node() {
+        assert_optimizes_to(
+            |b| {
+                b.set_last_node(NodeId(1));
+                b.set_text("a", MaybeId::LastNode);
+                b.set_last_node(NodeId(1));
+                b.set_text("b", MaybeId::LastNode);
+            },
+            |b| {
+                b.set_last_node(NodeId(1));
+                b.set_text("a", MaybeId::LastNode);
+                b.set_text("b", MaybeId::LastNode);
+            },
+        );
+    }
+
+    #[test]
+    fn keeps_set_last_node_after_the_cursor_changes() {
+        assert_unchanged(|b| {
+            b.set_last_node(NodeId(1));
+            b.set_text("a", MaybeId::LastNode);
+            b.pop_cursor();
+            b.set_last_node(NodeId(1));
+            b.set_text("b", MaybeId::LastNode);
+        });
+        assert_unchanged(|b| {
+            b.set_last_node(NodeId(1));
+            b.set_text("a", MaybeId::LastNode);
+            b.store_paths(MaybeId::Node(NodeId(0)), &[(&[0], NodeId(1))]);
+            b.set_last_node(NodeId(1));
+            b.set_text("b", MaybeId::LastNode);
+        });
+    }
+
+    #[test]
+    fn removes_dead_cursor_moves() {
+        assert_optimizes_to(
+            |b| {
+                b.first_child();
+                b.next_sibling();
+                b.navigate(&[1, 2]);
+                b.set_last_node(NodeId(2));
+                b.set_text("a", MaybeId::LastNode);
+            },
+            |b| {
+                b.set_last_node(NodeId(2));
+                b.set_text("a", MaybeId::LastNode);
+            },
+        );
+    }
+
+    #[test]
+    fn keeps_cursor_moves_at_the_end_of_the_batch() {
+        // the last node is kept between batches
+        assert_unchanged(|b| {
+            b.set_last_node(NodeId(1));
+            b.first_child();
+        });
+    }
+
+    #[test]
+    fn keeps_cursor_moves_that_are_read() {
+        // the node stack reads the last node
+        assert_unchanged(|b| {
+            b.first_child();
+            b.push_root(MaybeId::LastNode);
+            b.set_last_node(NodeId(2));
+            b.append_children(MaybeId::LastNode, 1);
+        });
+        // the cursor stack reads the last node
+        assert_unchanged(|b| {
+            b.first_child();
+            b.push_cursor();
+            b.set_last_node(NodeId(2));
+        });
+        // storing paths from the last node reads the last node
+        assert_unchanged(|b| {
+            b.last_child();
+            b.store_paths(MaybeId::LastNode, &[(&[0], NodeId(1))]);
+            b.set_last_node(NodeId(2));
+        });
+    }
+}
@@ -0,0 +1,112 @@
+//! `data-*` and `aria-*` attributes that are encoded as a prefix byte followed by the rest of the name.
+//!
+//! Hyphenated names can only be encoded with a single byte if they are in the [`Attribute`](crate::Attribute) table or added to [`CustomNames`](crate::CustomNames). Any other `data-*` or `aria-*` attribute would be sent as a full string, so a [`PrefixedAttribute`] only sends the part of the name after the prefix.
+//! The well-known ARIA attributes like [`Attribute::aria_label`](crate::Attribute::aria_label) are already in the built-in attribute table and are encoded with a single byte.
+
+use crate::{
+    attribute::AnyAttribute,
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+    extended::EXTENDED,
+    IntoAttribue,
+};
+
+/// The byte that marks an attribute that is encoded as `data-` followed by the suffix
+pub(crate) const DATA_PREFIX: u8 = 251;
+
+/// The byte that marks an attribute that is encoded as `aria-` followed by the suffix
+pub(crate) const ARIA_PREFIX: u8 = 250;
+
+const _: () = assert!(
+    ARIA_PREFIX < DATA_PREFIX && DATA_PREFIX < EXTENDED,
+    "prefixes must not overlap the other reserved attribute bytes"
+);
+
+/// The prefix of a [`PrefixedAttribute`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AttributePrefix {
+    /// `data-`
+    Data,
+    /// `aria-`
+    Aria,
+}
+
+impl AttributePrefix {
+    /// The prefix as it is written in html
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            AttributePrefix::Data => "data-",
+            AttributePrefix::Aria => "aria-",
+        }
+    }
+
+    /// The byte the prefix is encoded as
+    pub(crate) const fn byte(self) -> u8 {
+        match self {
+            AttributePrefix::Data => DATA_PREFIX,
+            AttributePrefix::Aria => ARIA_PREFIX,
+        }
+    }
+}
+
+/// An attribute with a `data-` or `aria-` prefix. The prefix is encoded as a single byte and only the suffix is sent as a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PrefixedAttribute<'a> {
+    pub prefix: AttributePrefix,
+    pub suffix: &'a str,
+}
+
+/// A `data-*` attribute. `data("row-id")` is the attribute `data-row-id`.
+pub const fn data(suffix: &str) -> PrefixedAttribute<'_> {
+    PrefixedAttribute {
+        prefix: AttributePrefix::Data,
+        suffix,
+    }
+}
+
+/// An `aria-*` attribute. `aria("label")` is the attribute `aria-label`.
+///
+/// Well-known ARIA attributes like [`Attribute::aria_label`](crate::Attribute::aria_label) are encoded with a single byte, so prefer them when the name is known ahead of time.
+pub const fn aria(suffix: &str) -> PrefixedAttribute<'_> {
+    PrefixedAttribute {
+        prefix: AttributePrefix::Aria,
+        suffix,
+    }
+}
+
+impl core::fmt::Display for PrefixedAttribute<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.prefix.as_str())?;
+        f.write_str(self.suffix)
+    }
+}
+
+impl<'a, 'b> PrefixedAttribute<'a> {
+    /// Turn into an [`AnyAttribute`] in a const context
+    pub const fn any_attr_const(self) -> AnyAttribute<'a, 'b> {
+        AnyAttribute::Prefixed(self)
+    }
+}
+
+impl<'a, 'b> IntoAttribue<'a, 'b> for PrefixedAttribute<'a> {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(false);
+        v.encode_bool(false);
+        v.msg.try_push(self.prefix.byte())?;
+        v.encode_cachable_str(self.suffix)
+    }
+
+    unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
+        v.encode_u8_prealloc(self.prefix.byte());
+        v.encode_str_prealloc(self.suffix)
+    }
+}
+
+impl<'a, 'b> From<PrefixedAttribute<'a>> for AnyAttribute<'a, 'b> {
+    fn from(a: PrefixedAttribute<'a>) -> Self {
+        AnyAttribute::Prefixed(a)
+    }
+}
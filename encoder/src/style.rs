@@ -0,0 +1,252 @@
+#![allow(non_camel_case_types)]
+
+use self::sealed::Sealed;
+use crate::{
+    batch::Batch,
+    buffer::{Buffer, BufferFull},
+};
+
+/// The byte that marks a style property that is encoded as a string
+pub(crate) const STR_STYLE: u8 = 255;
+
+const _: () = assert!(
+    Style::ALL.len() <= STR_STYLE as usize,
+    "styles must not use the string discriminant"
+);
+
+mod sealed {
+    use crate::Style;
+
+    pub trait Sealed {}
+
+    impl Sealed for Style {}
+    impl Sealed for &str {}
+}
+
+/// Any style property name
+#[derive(Clone, Copy)]
+pub enum AnyStyle<'a> {
+    Style(Style),
+    Str(&'a str),
+}
+
+impl AnyStyle<'_> {
+    pub(crate) unsafe fn encode_u8_discriminant_prealloc<B: Buffer>(
+        self,
+        v: &mut Batch<B>,
+    ) -> Result<(), BufferFull> {
+        match self {
+            AnyStyle::Style(s) => {
+                v.encode_u8_prealloc(s as u8);
+                Ok(())
+            }
+            AnyStyle::Str(s) => {
+                v.encode_u8_prealloc(STR_STYLE);
+                v.encode_str_prealloc(s)
+            }
+        }
+    }
+
+    pub(crate) fn size_with_u8_discriminant(&self) -> usize {
+        match self {
+            AnyStyle::Style(_) => 1,
+            AnyStyle::Str(_) => 1 + 2,
+        }
+    }
+}
+
+/// Anything that can be turned into a style property name
+pub trait IntoStyle<'a>: Sealed + Into<AnyStyle<'a>> {
+    /// If the style property can be encoded in a single byte
+    const SINGLE_BYTE: bool = false;
+
+    /// Encode the style property into the message channel
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull>;
+
+    /// Encode the style property into the message channel with memory pre-allocated
+    ///
+    /// # Safety
+    ///
+    /// This is only safe if the batch is preallocated to the correct size
+    unsafe fn encode_prealloc<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull>
+    where
+        Self: Sized,
+    {
+        self.encode(v)
+    }
+}
+
+impl<'a> Style {
+    /// Turn into an [`AnyStyle`] in a const context
+    pub const fn any_style_const(self) -> AnyStyle<'a> {
+        AnyStyle::Style(self)
+    }
+}
+
+impl<'a> IntoStyle<'a> for Style {
+    const SINGLE_BYTE: bool = true;
+
+    #[inline(always)]
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(false);
+        v.msg.try_push(self as u8)
+    }
+
+    #[inline(always)]
+    unsafe fn encode_prealloc<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(false);
+        unsafe {
+            v.encode_u8_prealloc(self as u8);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<Style> for AnyStyle<'a> {
+    fn from(s: Style) -> Self {
+        AnyStyle::Style(s)
+    }
+}
+
+/// Any property name including custom properties like `--main-color`
+impl<'a> IntoStyle<'a> for &'a str {
+    fn encode<B: Buffer>(self, v: &mut Batch<B>) -> Result<(), BufferFull> {
+        v.encode_bool(true);
+        v.encode_cachable_str(self)
+    }
+}
+
+impl<'a> From<&'a str> for AnyStyle<'a> {
+    fn from(s: &'a str) -> Self {
+        AnyStyle::Str(s)
+    }
+}
+
+name_table! {
+    /// Common css properties
+    /// These are the style properties that can be encoded with a single byte so they are more efficient (but less flexable) than a &str property
+    pub enum Style;
+    /// The error returned when parsing a name that is not a built-in [`Style`]
+    pub struct ParseStyleError("not a built-in style property");
+    align_content = "align-content",
+    align_items = "align-items",
+    align_self = "align-self",
+    animation,
+    animation_delay = "animation-delay",
+    animation_duration = "animation-duration",
+    animation_name = "animation-name",
+    animation_play_state = "animation-play-state",
+    aspect_ratio = "aspect-ratio",
+    backdrop_filter = "backdrop-filter",
+    background,
+    background_color = "background-color",
+    background_image = "background-image",
+    background_position = "background-position",
+    background_repeat = "background-repeat",
+    background_size = "background-size",
+    border,
+    border_bottom = "border-bottom",
+    border_color = "border-color",
+    border_left = "border-left",
+    border_radius = "border-radius",
+    border_right = "border-right",
+    border_style = "border-style",
+    border_top = "border-top",
+    border_width = "border-width",
+    bottom,
+    box_shadow = "box-shadow",
+    box_sizing = "box-sizing",
+    caret_color = "caret-color",
+    clip_path = "clip-path",
+    color,
+    column_gap = "column-gap",
+    content,
+    cursor,
+    display,
+    fill,
+    filter,
+    flex,
+    flex_basis = "flex-basis",
+    flex_direction = "flex-direction",
+    flex_grow = "flex-grow",
+    flex_shrink = "flex-shrink",
+    flex_wrap = "flex-wrap",
+    font,
+    font_family = "font-family",
+    font_size = "font-size",
+    font_style = "font-style",
+    font_weight = "font-weight",
+    gap,
+    grid_area = "grid-area",
+    grid_column = "grid-column",
+    grid_row = "grid-row",
+    grid_template_areas = "grid-template-areas",
+    grid_template_columns = "grid-template-columns",
+    grid_template_rows = "grid-template-rows",
+    height,
+    inset,
+    justify_content = "justify-content",
+    justify_items = "justify-items",
+    justify_self = "justify-self",
+    left,
+    letter_spacing = "letter-spacing",
+    line_height = "line-height",
+    list_style = "list-style",
+    margin,
+    margin_bottom = "margin-bottom",
+    margin_left = "margin-left",
+    margin_right = "margin-right",
+    margin_top = "margin-top",
+    max_height = "max-height",
+    max_width = "max-width",
+    min_height = "min-height",
+    min_width = "min-width",
+    mix_blend_mode = "mix-blend-mode",
+    object_fit = "object-fit",
+    object_position = "object-position",
+    opacity,
+    order,
+    outline,
+    outline_offset = "outline-offset",
+    overflow,
+    overflow_wrap = "overflow-wrap",
+    overflow_x = "overflow-x",
+    overflow_y = "overflow-y",
+    padding,
+    padding_bottom = "padding-bottom",
+    padding_left = "padding-left",
+    padding_right = "padding-right",
+    padding_top = "padding-top",
+    pointer_events = "pointer-events",
+    position,
+    resize,
+    right,
+    row_gap = "row-gap",
+    scroll_behavior = "scroll-behavior",
+    stroke,
+    stroke_width = "stroke-width",
+    text_align = "text-align",
+    text_decoration = "text-decoration",
+    text_overflow = "text-overflow",
+    text_shadow = "text-shadow",
+    text_transform = "text-transform",
+    top,
+    transform,
+    transform_origin = "transform-origin",
+    transition,
+    transition_delay = "transition-delay",
+    transition_duration = "transition-duration",
+    transition_property = "transition-property",
+    transition_timing_function = "transition-timing-function",
+    translate,
+    rotate,
+    scale,
+    user_select = "user-select",
+    vertical_align = "vertical-align",
+    visibility,
+    white_space = "white-space",
+    width,
+    will_change = "will-change",
+    word_break = "word-break",
+    z_index = "z-index",
+}
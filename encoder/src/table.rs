@@ -0,0 +1,132 @@
+/// Generates an enum of names that can be encoded in a single byte from a table of variants.
+///
+/// The discriminant of each variant is its position in the table, and the interpreter looks up names by that discriminant. New names must only be added to the end of a table and the table in the interpreter must be updated with them.
+/// A variant is named the same as its ident unless a name is given with `variant = "name"`.
+macro_rules! name_table {
+    (
+        $(#[$enum_meta:meta])*
+        pub enum $name:ident;
+        $(#[$error_meta:meta])*
+        pub struct $error:ident($message:literal);
+        $($(#[$meta:meta])* $variant:ident $(= $str:literal)?),* $(,)?
+    ) => {
+        $(#[$enum_meta])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(u8)]
+        pub enum $name {
+            $(
+                $(#[$meta])*
+                $variant
+            ),*
+        }
+
+        #[allow(deprecated)]
+        impl $name {
+            /// Every variant ordered by its discriminant
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            /// The name as it is written in html
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(
+                        $name::$variant => name_table!(@name $variant $($str)?),
+                    )*
+                }
+            }
+
+            /// Get the variant that is encoded as the byte
+            pub const fn from_u8(byte: u8) -> Option<Self> {
+                if (byte as usize) < Self::ALL.len() {
+                    Some(Self::ALL[byte as usize])
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        $(#[$error_meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $error;
+
+        impl core::fmt::Display for $error {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str($message)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl std::error::Error for $error {}
+
+        #[allow(deprecated)]
+        impl core::str::FromStr for $name {
+            type Err = $error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(match s {
+                    $(
+                        name_table!(@name $variant $($str)?) => $name::$variant,
+                    )*
+                    _ => return Err($error),
+                })
+            }
+        }
+    };
+    (@name $variant:ident $str:literal) => {
+        $str
+    };
+    (@name $variant:ident) => {
+        stringify!($variant)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{fmt::Debug, str::FromStr};
+
+    use alloc::string::ToString;
+
+    use crate::{
+        extended::{MathMlAttribute, MathMlElement, SvgAttribute, SvgElement},
+        namespace::KnownNamespace,
+        Attribute, Element, Style,
+    };
+
+    /// Every variant is found by its byte and by its name
+    fn assert_round_trips<T>(all: &[T], from_u8: fn(u8) -> Option<T>)
+    where
+        T: Copy + Debug + PartialEq + core::fmt::Display + FromStr,
+    {
+        for (byte, variant) in all.iter().enumerate() {
+            assert_eq!(from_u8(byte as u8), Some(*variant));
+            assert_eq!(variant.to_string().parse::<T>().ok(), Some(*variant));
+        }
+        assert_eq!(from_u8(all.len() as u8), None);
+    }
+
+    #[test]
+    fn names_round_trip() {
+        assert_round_trips(Element::ALL, Element::from_u8);
+        assert_round_trips(Attribute::ALL, Attribute::from_u8);
+        assert_round_trips(Style::ALL, Style::from_u8);
+        assert_round_trips(KnownNamespace::ALL, KnownNamespace::from_u8);
+        assert_round_trips(SvgElement::ALL, SvgElement::from_u8);
+        assert_round_trips(MathMlElement::ALL, MathMlElement::from_u8);
+        assert_round_trips(SvgAttribute::ALL, SvgAttribute::from_u8);
+        assert_round_trips(MathMlAttribute::ALL, MathMlAttribute::from_u8);
+    }
+
+    #[test]
+    fn variants_are_named_like_css() {
+        assert_eq!(Style::color.as_str(), "color");
+        assert_eq!(Style::align_content.as_str(), "align-content");
+        assert_eq!("align-content".parse(), Ok(Style::align_content));
+        assert_eq!("align_content".parse::<Style>().ok(), None);
+        assert_eq!("--main-color".parse::<Style>().ok(), None);
+    }
+}
@@ -4,10 +4,13 @@ use bumpalo::Bump;
 use proc_macro::TokenStream;
 use quote::quote;
 use sledgehammer_encoder::{
+    aria,
     attribute::AnyAttribute,
     batch::{Batch, FinalizedBatch},
+    data,
     element::AnyElement,
-    Attribute, Element, ElementBuilder, NodeBuilder, NodeId, TextBuilder,
+    Attribute, CustomNames, Element, ElementBuilder, InNamespace, KnownNamespace, MathMlAttribute,
+    MathMlElement, Namespace, NodeBuilder, NodeId, SvgAttribute, SvgElement, TextBuilder,
 };
 use syn::{Expr, Lit};
 use syn_rsx::{parse, Node, NodeType};
@@ -96,6 +99,30 @@ fn as_str_lit(expr: &Expr) -> String {
 /// let world = "planet";
 /// assert_eq!(html!(<div>"hello "{world}</div>), "<div>hello planet</div>");
 /// ```
+///
+/// # SVG and MathML
+///
+/// Elements inside of an `svg` or `math` element are created in the SVG or MathML namespace. Their elements and attributes are encoded with the extended SVG and MathML tables.
+///
+/// # Custom names
+///
+/// The root element can list the custom element and attribute names registered with `MsgChannel::with_custom_names` in the `sledgehammer-elements` and `sledgehammer-attributes` attributes, separated by spaces.
+///
+/// Custom names are encoded by their position, so the names must be listed in the same order they were added to the `CustomNames` of the channel, and the lists must start with the first name that was added. The macro runs at compile time and cannot see the `CustomNames` of the channel, so this is not checked. A list in a different order makes the interpreter create the wrong elements and attributes.
+///
+/// ```ignore
+/// use sledgehammer::{CustomNames, MsgChannel, StaticBatch};
+/// use sledgehammer_prebuild::html;
+///
+/// let mut names = CustomNames::new();
+/// names.element("my-grid");
+/// names.element("my-grid-cell");
+/// names.attribute("data-row-id");
+/// let mut channel = MsgChannel::with_custom_names(&names);
+///
+/// channel.run_batch(html!(<my-grid sledgehammer-elements="my-grid my-grid-cell" sledgehammer-attributes="data-row-id"><my-grid-cell data-row-id="1"/></my-grid>));
+/// channel.flush();
+/// ```
 #[proc_macro]
 pub fn html(tokens: TokenStream) -> TokenStream {
     match parse(tokens) {
@@ -103,10 +130,19 @@ pub fn html(tokens: TokenStream) -> TokenStream {
             let mut builder = None;
             walk_nodes(&nodes, &mut builder);
             match builder {
-                Some(builder) => {
+                Some(mut builder) => {
+                    let custom_elements = take_attribute(&mut builder, "sledgehammer-elements");
+                    let custom_attributes = take_attribute(&mut builder, "sledgehammer-attributes");
+                    let mut names = CustomNames::new();
+                    for name in custom_elements.split_whitespace() {
+                        names.element(name);
+                    }
+                    for name in custom_attributes.split_whitespace() {
+                        names.attribute(name);
+                    }
                     let bump = Bump::new();
                     let builder = NodeInProgress::Element(builder);
-                    let builder = build_in_progress(&bump, &builder);
+                    let builder = build_in_progress(&bump, &builder, &names, Context::Html);
                     let mut batch = Batch::default();
                     match builder {
                         NodeBuilder::Text(txt) => batch.build_text_node(txt),
@@ -134,17 +170,103 @@ pub fn html(tokens: TokenStream) -> TokenStream {
     .into()
 }
 
-fn build_in_progress<'a>(allocator: &'a Bump, node: &'a NodeInProgress) -> NodeBuilder<'a> {
+/// Removes an attribute from the element and returns its value, or an empty string if the element does not have the attribute
+fn take_attribute(el: &mut ElementInProgress, name: &str) -> String {
+    match el.attributes.iter().position(|(attr, _)| attr == name) {
+        Some(idx) => el.attributes.remove(idx).1,
+        None => String::new(),
+    }
+}
+
+/// The namespace that the children of an element are created in
+#[derive(Clone, Copy)]
+enum Context {
+    Html,
+    Svg,
+    MathMl,
+}
+
+fn element<'a>(kind: &'a str, context: Context, names: &CustomNames) -> AnyElement<'a, 'a> {
+    match context {
+        Context::Html => match Element::from_str(kind) {
+            Ok(el) => AnyElement::Element(el),
+            Err(_) => match names.get_element(kind) {
+                Some(el) => AnyElement::Custom(el),
+                None => match kind {
+                    "svg" => AnyElement::Svg(SvgElement::svg),
+                    "math" => AnyElement::MathMl(MathMlElement::math),
+                    _ => AnyElement::Str(kind),
+                },
+            },
+        },
+        Context::Svg => match SvgElement::from_str(kind) {
+            Ok(el) => AnyElement::Svg(el),
+            Err(_) => {
+                AnyElement::InNamespaceStr(InNamespace(kind, Namespace::Known(KnownNamespace::svg)))
+            }
+        },
+        Context::MathMl => match MathMlElement::from_str(kind) {
+            Ok(el) => AnyElement::MathMl(el),
+            Err(_) => AnyElement::InNamespaceStr(InNamespace(
+                kind,
+                Namespace::Known(KnownNamespace::mathml),
+            )),
+        },
+    }
+}
+
+fn attribute<'a>(name: &'a str, context: Context, names: &CustomNames) -> AnyAttribute<'a, 'a> {
+    if let Ok(a) = Attribute::from_str(name) {
+        return AnyAttribute::Attribute(a);
+    }
+    let extended = match context {
+        Context::Html => None,
+        Context::Svg => SvgAttribute::from_str(name).ok().map(AnyAttribute::Svg),
+        Context::MathMl => MathMlAttribute::from_str(name)
+            .ok()
+            .map(AnyAttribute::MathMl),
+    };
+    match extended {
+        Some(a) => a,
+        None => match names.get_attribute(name) {
+            Some(a) => AnyAttribute::Custom(a),
+            None => {
+                if let Some(suffix) = name.strip_prefix("data-") {
+                    AnyAttribute::Prefixed(data(suffix))
+                } else if let Some(suffix) = name.strip_prefix("aria-") {
+                    AnyAttribute::Prefixed(aria(suffix))
+                } else {
+                    AnyAttribute::Str(name)
+                }
+            }
+        },
+    }
+}
+
+fn build_in_progress<'a>(
+    allocator: &'a Bump,
+    node: &'a NodeInProgress,
+    names: &CustomNames,
+    context: Context,
+) -> NodeBuilder<'a> {
     match node {
         NodeInProgress::Element(el) => {
-            let mut builder = ElementBuilder::new(match Element::from_str(&el.kind) {
-                Ok(el) => AnyElement::Element(el),
-                Err(_) => AnyElement::Str(&el.kind),
-            });
+            let kind = element(&el.kind, context, names);
+            let context = match kind {
+                AnyElement::Svg(_) => Context::Svg,
+                AnyElement::MathMl(_) => Context::MathMl,
+                _ => context,
+            };
+            // the children of a foreign object are html
+            let children_context = match kind {
+                AnyElement::Svg(SvgElement::foreignObject) => Context::Html,
+                _ => context,
+            };
+            let mut builder = ElementBuilder::new(kind);
             let children: Vec<_> = el
                 .children
                 .iter()
-                .map(|node| build_in_progress(allocator, node))
+                .map(|node| build_in_progress(allocator, node, names, children_context))
                 .collect();
             builder = builder.children(allocator.alloc(children));
             let mut id = None;
@@ -157,10 +279,7 @@ fn build_in_progress<'a>(allocator: &'a Bump, node: &'a NodeInProgress) -> NodeB
                         None
                     } else {
                         Some((
-                            match Attribute::from_str(attr) {
-                                Ok(a) => AnyAttribute::Attribute(a),
-                                Err(_) => AnyAttribute::Str(attr),
-                            },
+                            attribute(attr, context, names),
                             &*allocator.alloc_str(value),
                         ))
                     }
@@ -15,11 +15,31 @@ function exOp() {
     switch (op & 0x1F) {
         // first child
         case 0:
-            inptr.lastNode = inptr.lastNode.firstChild;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the last child is used
+            if (op & 0x20) {
+                inptr.lastNode = inptr.lastNode.lastChild;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the nth child is used
+            else if (op & 0x40) {
+                inptr.lastNode = inptr.lastNode.childNodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                inptr.lastNode = inptr.lastNode.firstChild;
+            }
             break;
         // next sibling
         case 1:
-            inptr.lastNode = inptr.lastNode.nextSibling;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the previous sibling is used
+            if (op & 0x20) {
+                inptr.lastNode = inptr.lastNode.previousSibling;
+            }
+            else {
+                inptr.lastNode = inptr.lastNode.nextSibling;
+            }
             break;
         // parent
         case 2:
@@ -52,8 +72,14 @@ function exOp() {
             else {
                 parent = inptr.lastNode;
             }
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if the children are popped from the node stack
+            if (op & 0x80) {
+                parent.append(...inptr.stack.splice(inptr.stack.length - inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
             // the second bool is encoded as op & (1 << 6)
-            if (op & 0x40) {
+            else if (op & 0x40) {
                 parent.appendChild(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
                 inptr.u8BufPos += 4;
             }
@@ -73,15 +99,23 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
-                        nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
-                        inptr.u8BufPos += 4;
-                    }
-                    else {
-                        nodes.push(inptr.lastNode);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.stack.splice(inptr.stack.length - inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                }
+                else {
+                    numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
+                            nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
+                            inptr.u8BufPos += 4;
+                        }
+                        else {
+                            nodes.push(inptr.lastNode);
+                        }
                     }
                 }
                 parent.replaceWith(...nodes);
@@ -109,15 +143,23 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
-                        nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
-                        inptr.u8BufPos += 4;
-                    }
-                    else {
-                        nodes.push(inptr.lastNode);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.stack.splice(inptr.stack.length - inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                }
+                else {
+                    numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
+                            nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
+                            inptr.u8BufPos += 4;
+                        }
+                        else {
+                            nodes.push(inptr.lastNode);
+                        }
                     }
                 }
                 parent.after(...nodes);
@@ -144,15 +186,23 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
-                        nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
-                        inptr.u8BufPos += 4;
-                    }
-                    else {
-                        nodes.push(inptr.lastNode);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.stack.splice(inptr.stack.length - inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                }
+                else {
+                    numNodes = inptr.view.getUint8(inptr.u8BufPos++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.view.getUint8(inptr.u8BufPos++, true)) {
+                            nodes.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
+                            inptr.u8BufPos += 4;
+                        }
+                        else {
+                            nodes.push(inptr.lastNode);
+                        }
                     }
                 }
                 parent.before(...nodes);
@@ -180,8 +230,15 @@ function exOp() {
             break;
         // create text node
         case 12:
-            inptr.lastNode = document.createTextNode(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
-            inptr.u8BufPos += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is a static string in wasm memory
+            if (op & 0x40) {
+                inptr.lastNode = document.createTextNode(inptr.decodeStaticStr());
+            }
+            else {
+                inptr.lastNode = document.createTextNode(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
                 inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)] = inptr.lastNode;
@@ -201,13 +258,19 @@ function exOp() {
         case 14:
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                id = inptr.view.getUint32(inptr.u8BufPos, true);
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
                 inptr.u8BufPos += 4;
-                inptr.nodes[id].textContent = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
-                inptr.u8BufPos += 2;
             }
             else {
-                inptr.lastNode.textContent = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                node = inptr.lastNode;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is a static string in wasm memory
+            if (op & 0x40) {
+                node.textContent = inptr.decodeStaticStr();
+            }
+            else {
+                node.textContent = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
                 inptr.u8BufPos += 2;
             }
             break;
@@ -224,33 +287,53 @@ function exOp() {
             // the second bool is encoded as op & (1 << 6)
             // first bool encodes if the attribute is a string
             if (op & 0x40) {
-                // the first two lengths
-                i = inptr.view.getUint32(inptr.u8BufPos, true);
-                inptr.u8BufPos += 4;
-                attr = inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF);
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    node.setAttributeNS(inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF0000) >>> 16), attr, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                    attr = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                    ns = inptr.decodeNs();
+                    node.setAttributeNS(ns, attr, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
                     inptr.u8BufPos += 2;
                 }
                 else {
+                    // the first two lengths
+                    i = inptr.view.getUint32(inptr.u8BufPos, true);
+                    inptr.u8BufPos += 4;
+                    attr = inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF);
                     node.setAttribute(attr, inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF0000) >>> 16));
                 }
             } else {
-                // the first length and attribute id or the attribute id and the first length
-                i = inptr.view.getUint32(inptr.u8BufPos, true);
-                // we only read 3 bytes out of the 4
-                inptr.u8BufPos += 3;
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    ns = inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF);
-                    node.setAttributeNS(ns, attrs[(i & 0xFF0000) >>> 16], inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                    attr = attrs[inptr.view.getUint8(inptr.u8BufPos++)];
+                    ns = inptr.decodeNs();
+                    node.setAttributeNS(ns, attr, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
                     inptr.u8BufPos += 2;
                 }
                 else {
-                    node.setAttribute(attrs[i & 0xFF], inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF00) >>> 8));
+                    // the attribute id and the first length
+                    i = inptr.view.getUint32(inptr.u8BufPos, true);
+                    if ((i & 0xFF) === 252) {
+                        // the attribute is in the extended table
+                        // we use all 4 bytes of i just read
+                        inptr.u8BufPos += 4;
+                        node.setAttribute(extAttrs[(i & 0xFF00) >>> 8], inptr.strings.substring(inptr.strPos, inptr.strPos += i >>> 16));
+                    }
+                    else if ((i & 0xFF) === 251 || (i & 0xFF) === 250) {
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        // we only read 3 bytes out of the 4
+                        inptr.u8BufPos += 3;
+                        attr = ((i & 0xFF) === 251 ? "data-" : "aria-") + inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF00) >>> 8);
+                        node.setAttribute(attr, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                        inptr.u8BufPos += 2;
+                    }
+                    else {
+                        // we only read 3 bytes out of the 4
+                        inptr.u8BufPos += 3;
+                        node.setAttribute(attrs[i & 0xFF], inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF00) >>> 8));
+                    }
                 }
             }
             break;
@@ -270,10 +353,9 @@ function exOp() {
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    i = inptr.view.getUint32(inptr.u8BufPos, true);
-                    inptr.u8BufPos += 4;
-                    attr = inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF);
-                    node.removeAttributeNS(inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF0000) >>> 16), attr);
+                    attr = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                    node.removeAttributeNS(inptr.decodeNs(), attr);
                 } else {
                     node.removeAttribute(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
                     inptr.u8BufPos += 2;
@@ -282,14 +364,20 @@ function exOp() {
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    i = inptr.view.getUint32(inptr.u8BufPos, true);
-                    // we only read 3 bytes out of the 4
-                    inptr.u8BufPos += 3;
-                    attr = attrs[i & 0xFF];
-                    node.removeAttributeNS(inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF00) >>> 8), attr);
+                    attr = attrs[inptr.view.getUint8(inptr.u8BufPos++)];
+                    node.removeAttributeNS(inptr.decodeNs(), attr);
                 }
                 else {
-                    node.removeAttribute(attrs[inptr.view.getUint8(inptr.u8BufPos++)]);
+                    attr = inptr.view.getUint8(inptr.u8BufPos++);
+                    if (attr === 251 || attr === 250) {
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        node.removeAttribute((attr === 251 ? "data-" : "aria-") + inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                        inptr.u8BufPos += 2;
+                    }
+                    else {
+                        // the attribute is either in the extended table or the built-in table
+                        node.removeAttribute(attr === 252 ? extAttrs[inptr.view.getUint8(inptr.u8BufPos++)] : attrs[attr]);
+                    }
                 }
             }
             break;
@@ -303,9 +391,24 @@ function exOp() {
             else {
                 node = inptr.lastNode;
             }
-            i = inptr.view.getUint32(inptr.u8BufPos, true);
-            inptr.u8BufPos += 4;
-            node.style.setProperty(inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF), inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF0000) >>> 16));
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is a string
+            if (op & 0x40) {
+                i = inptr.view.getUint32(inptr.u8BufPos, true);
+                inptr.u8BufPos += 4;
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the property is important
+                node.style.setProperty(inptr.strings.substring(inptr.strPos, inptr.strPos += i & 0xFFFF), inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF0000) >>> 16), op & 0x80 ? "important" : "");
+            }
+            else {
+                // the property id and the value length
+                i = inptr.view.getUint32(inptr.u8BufPos, true);
+                // we only read 3 bytes out of the 4
+                inptr.u8BufPos += 3;
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the property is important
+                node.style.setProperty(styles[i & 0xFF], inptr.strings.substring(inptr.strPos, inptr.strPos += (i & 0xFFFF00) >>> 8), op & 0x80 ? "important" : "");
+            }
             break;
         // remove style
         case 18:
@@ -317,18 +420,27 @@ function exOp() {
             else {
                 node = inptr.lastNode;
             }
-            node.style.removeProperty(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
-            inptr.u8BufPos += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is a string
+            if (op & 0x40) {
+                node.style.removeProperty(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
+            else {
+                node.style.removeProperty(styles[inptr.view.getUint8(inptr.u8BufPos++)]);
+            }
             break;
         // clone node
         case 19:
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if the clone is shallow
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                inptr.lastNode = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)].cloneNode(true);
+                inptr.lastNode = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)].cloneNode(!(op & 0x80));
                 inptr.u8BufPos += 4;
             }
             else {
-                inptr.lastNode = inptr.lastNode.cloneNode(true);
+                inptr.lastNode = inptr.lastNode.cloneNode(!(op & 0x80));
             }
             // the second bool is encoded as op & (1 << 6)
             if (op & 0x40) {
@@ -336,6 +448,233 @@ function exOp() {
                 inptr.u8BufPos += 4;
             }
             break;
+        // set styles
+        case 21:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            numAttributes = inptr.view.getUint8(inptr.u8BufPos++);
+            for (i = 0; i < numAttributes; i++) {
+                j = inptr.view.getUint8(inptr.u8BufPos++);
+                if (j === 255) {
+                    // the property is encoded as a string
+                    name = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                }
+                else {
+                    name = styles[j];
+                }
+                node.style.setProperty(name, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
+            break;
+        // set css text
+        case 22:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            node.style.cssText = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+            inptr.u8BufPos += 2;
+            break;
+        // toggle attribute
+        case 23:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            attr = inptr.decodeAttribute();
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the attribute is toggled
+            if (op & 0x40) {
+                value = ns === null ? !node.hasAttribute(attr) : !node.hasAttributeNS(ns, attr);
+            }
+            else {
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the attribute is added or removed
+                value = op & 0x80;
+            }
+            if (value) {
+                if (ns === null) {
+                    node.setAttribute(attr, "");
+                }
+                else {
+                    node.setAttributeNS(ns, attr, "");
+                }
+            }
+            else if (ns === null) {
+                node.removeAttribute(attr);
+            }
+            else {
+                node.removeAttributeNS(ns, attr);
+            }
+            break;
+        // set attributes
+        case 24:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            numAttributes = inptr.view.getUint8(inptr.u8BufPos++);
+            for (i = 0; i < numAttributes; i++) {
+                name = inptr.decodeAttribute();
+                value = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                inptr.u8BufPos += 2;
+                if (ns === null) {
+                    node.setAttribute(name, value);
+                }
+                else {
+                    node.setAttributeNS(ns, name, value);
+                }
+            }
+            break;
+        // set attribute many
+        case 25:
+            name = inptr.decodeAttribute();
+            numNodes = inptr.view.getUint16(inptr.u8BufPos, true);
+            inptr.u8BufPos += 2;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if every node gets the same value
+            if (op & 0x20) {
+                value = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                inptr.u8BufPos += 2;
+            }
+            for (i = 0; i < numNodes; i++) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+                if (!(op & 0x20)) {
+                    value = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                    inptr.u8BufPos += 2;
+                }
+                if (ns === null) {
+                    node.setAttribute(name, value);
+                }
+                else {
+                    node.setAttributeNS(ns, name, value);
+                }
+            }
+            break;
+        // dataset
+        case 26:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            name = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+            inptr.u8BufPos += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is removed
+            if (op & 0x40) {
+                delete node.dataset[name];
+            }
+            else {
+                node.dataset[name] = inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true));
+                inptr.u8BufPos += 2;
+            }
+            break;
+        // edit text
+        case 27:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                inptr.u8BufPos += 4;
+            }
+            else {
+                node = inptr.lastNode;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is inserted at an offset
+            if (op & 0x40) {
+                i = inptr.view.getUint32(inptr.u8BufPos, true);
+                inptr.u8BufPos += 4;
+                node.insertData(i, inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if text is deleted
+            else if (op & 0x80) {
+                node.deleteData(inptr.view.getUint32(inptr.u8BufPos, true), inptr.view.getUint32(inptr.u8BufPos + 4, true));
+                inptr.u8BufPos += 8;
+            }
+            else {
+                node.appendData(inptr.strings.substring(inptr.strPos, inptr.strPos += inptr.view.getUint16(inptr.u8BufPos, true)));
+                inptr.u8BufPos += 2;
+            }
+            break;
+        // push root
+        case 28:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                inptr.stack.push(inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)]);
+                inptr.u8BufPos += 4;
+            }
+            else {
+                inptr.stack.push(inptr.lastNode);
+            }
+            break;
+        // navigate
+        case 29:
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the nodes at many paths are stored with ids
+            if (op & 0x20) {
+                // the second bool is encoded as op & (1 << 6)
+                if (op & 0x40) {
+                    node = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)];
+                    inptr.u8BufPos += 4;
+                }
+                else {
+                    node = inptr.lastNode;
+                }
+                inptr.storePaths(node);
+            }
+            else {
+                inptr.lastNode = inptr.walkPath(inptr.lastNode);
+            }
+            break;
+        // cursor
+        case 30:
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the last node is restored instead of saved
+            if (op & 0x20) {
+                inptr.lastNode = inptr.cursors.pop();
+            }
+            else {
+                inptr.cursors.push(inptr.lastNode);
+            }
+            break;
+        // clone node with ids
+        case 31:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                inptr.lastNode = inptr.nodes[inptr.view.getUint32(inptr.u8BufPos, true)].cloneNode(true);
+                inptr.u8BufPos += 4;
+            }
+            else {
+                inptr.lastNode = inptr.lastNode.cloneNode(true);
+            }
+            inptr.storePaths(inptr.lastNode);
+            break;
         default:
             break;
     }
@@ -346,6 +685,8 @@ export class JsInterpreter {
         this.lastNode;
         this.nodes = [];
         this.parents = [];
+        this.stack = [];
+        this.cursors = [];
         this.UpdateMemory(mem);
         this.last_start_pos;
         this.last_str_start;
@@ -380,9 +721,29 @@ export class JsInterpreter {
             if (metadata & 0x02) {
                 this.last_str_start = this.view.getUint32(this.str_ptr_ptr, true);
             }
+            // utf-16 strings are already code units, so they can be turned into a string without a TextDecoder
+            if (metadata & 0x10) {
+                pos = this.last_str_start;
+                end = pos + len;
+                this.strings = "";
+                if (pos & 1) {
+                    // a Uint16Array must be aligned to two bytes
+                    while (pos < end) {
+                        this.strings += String.fromCharCode(this.view.getUint16(pos, true));
+                        pos += 2;
+                    }
+                }
+                else {
+                    out = new Uint16Array(this.view.buffer, pos, len >>> 1);
+                    // passing too many arguments to fromCharCode at once overflows the stack
+                    for (i = 0; i < out.length; i += 4096) {
+                        this.strings += String.fromCharCode.apply(null, out.subarray(i, i + 4096));
+                    }
+                }
+            }
             // for small strings decoding them in javascript to avoid the overhead of native calls is faster
             // the fourth boolean contains information about whether the string is all ascii or utf8 and small
-            if (metadata & 0x08) {
+            else if (metadata & 0x08) {
                 pos = this.last_str_start;
                 this.strings = "";
                 endRounded = pos + ((len / 4) | 0) * 4;
@@ -434,25 +795,32 @@ export class JsInterpreter {
         element = j & 0xFF;
         switch (element) {
             case 255:
-                // the element is encoded as an enum and the namespace is encoded as a string
-                // we use all 4 bytes of i just read
-                this.u8BufPos += 4;
-                element = document.createElement(els[(j & 0xFF00) >>> 8], this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF0000) >>> 16));
-                return element;
+                // the element is encoded as an enum followed by the namespace
+                // we use 2 bytes of j just read
+                this.u8BufPos += 2;
+                element = els[(j & 0xFF00) >>> 8];
+                return document.createElementNS(this.decodeNs(), element);
             case 254:
                 // the element is encoded as a string
                 // we use 3 bytes of i just read
                 this.u8BufPos += 3;
                 element = document.createElement(this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF00) >>> 8));
                 return element;
+            case 252:
+                // the element is in the extended table and its namespace is implied by the table
+                // we use 2 bytes of j just read
+                this.u8BufPos += 2;
+                element = (j & 0xFF00) >>> 8;
+                if (element < svgEls.length) {
+                    return document.createElementNS(nss[0], svgEls[element]);
+                }
+                return document.createElementNS(nss[1], mathEls[element - svgEls.length]);
             case 253:
-                // the element and namespace are encoded as strings
-                // we use 3 bytes of i just read
+                // the element is encoded as a string followed by the namespace
+                // we use 3 bytes of j just read
                 this.u8BufPos += 3;
                 element = this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF00) >>> 8);
-                element = document.createElementNS(this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos, true)), element);
-                this.u8BufPos += 2;
-                return element;
+                return document.createElementNS(this.decodeNs(), element);
             default:
                 this.u8BufPos++;
                 // the element is encoded as an enum
@@ -486,11 +854,13 @@ export class JsInterpreter {
                 attr = j & 0xFF;
                 switch (attr) {
                     case 255:
-                        // the attribute is encoded as an enum and the namespace is encoded as a string
-                        // we use all 4 bytes of j just read
-                        this.u8BufPos += 4;
-                        attr = attrs[this.view.getUint8((j & 0xFF00) >>> 8)];
-                        parent_element.setAttributeNS(this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF0000) >>> 16), attr);
+                        // the attribute is encoded as an enum followed by the namespace
+                        // we use 2 bytes of j just read
+                        this.u8BufPos += 2;
+                        attr = attrs[(j & 0xFF00) >>> 8];
+                        ns = this.decodeNs();
+                        parent_element.setAttributeNS(ns, attr, this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos, true)));
+                        this.u8BufPos += 2;
                         break;
                     case 254:
                         // the attribute is encoded as a string
@@ -501,15 +871,29 @@ export class JsInterpreter {
                         attr = this.strings.substring(this.strPos, this.strPos += j & 0xFFFF);
                         parent_element.setAttribute(attr, this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF0000) >>> 16));
                         break;
+                    case 252:
+                        // the attribute is in the extended table
+                        // we use all 4 bytes of j just read
+                        this.u8BufPos += 4;
+                        parent_element.setAttribute(extAttrs[(j & 0xFF00) >>> 8], this.strings.substring(this.strPos, this.strPos += j >>> 16));
+                        break;
+                    case 251:
+                    case 250:
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        // we use 3 bytes of j just read
+                        this.u8BufPos += 3;
+                        attr = (attr === 251 ? "data-" : "aria-") + this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF00) >>> 8);
+                        parent_element.setAttribute(attr, this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos, true)));
+                        this.u8BufPos += 2;
+                        break;
                     case 253:
-                        // the attribute and namespace are encoded as strings
+                        // the attribute is encoded as a string followed by the namespace
                         // we use 3 bytes of j just read
                         this.u8BufPos += 3;
                         attr = this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF00) >>> 8);
-                        j = this.view.getUint32(this.u8BufPos, true);
-                        this.u8BufPos += 4;
-                        ns = this.strings.substring(this.strPos, this.strPos += j & 0xFFFF);
-                        value = this.strings.substring(this.strPos, this.strPos += (j & 0xFFFF0000) >>> 16);
+                        ns = this.decodeNs();
+                        value = this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos, true));
+                        this.u8BufPos += 2;
                         parent_element.setAttributeNS(ns, attr, value);
                         break;
                     default:
@@ -529,11 +913,91 @@ export class JsInterpreter {
         }
     }
 
+    decodeNs() {
+        // the namespace is either an index into the namespace table or 255 followed by the length of the namespace string
+        const code = this.view.getUint8(this.u8BufPos++);
+        if (code === 255) {
+            len = this.view.getUint16(this.u8BufPos, true);
+            this.u8BufPos += 2;
+            return this.strings.substring(this.strPos, this.strPos += len);
+        }
+        return nss[code];
+    }
+
+    decodeAttribute() {
+        // the attribute is encoded with a u8 discriminant, the namespace is stored in ns
+        ns = null;
+        j = this.view.getUint8(this.u8BufPos++);
+        switch (j) {
+            case 255:
+                attr = attrs[this.view.getUint8(this.u8BufPos++)];
+                ns = this.decodeNs();
+                return attr;
+            case 254:
+                this.u8BufPos += 2;
+                return this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos - 2, true));
+            case 253:
+                this.u8BufPos += 2;
+                attr = this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos - 2, true));
+                ns = this.decodeNs();
+                return attr;
+            case 252:
+                return extAttrs[this.view.getUint8(this.u8BufPos++)];
+            case 251:
+            case 250:
+                this.u8BufPos += 2;
+                return (j === 251 ? "data-" : "aria-") + this.strings.substring(this.strPos, this.strPos += this.view.getUint16(this.u8BufPos - 2, true));
+            default:
+                return attrs[j];
+        }
+    }
+
+    walkPath(node) {
+        // the path is a u8 length followed by the index of the child at each step
+        end = this.u8BufPos + 1 + this.view.getUint8(this.u8BufPos);
+        for (this.u8BufPos++; this.u8BufPos < end; this.u8BufPos++) {
+            node = node.childNodes[this.view.getUint8(this.u8BufPos)];
+        }
+        return node;
+    }
+
+    storePaths(root) {
+        // a u16 number of paths each followed by the id to store the node at the path with
+        numNodes = this.view.getUint16(this.u8BufPos, true);
+        this.u8BufPos += 2;
+        for (i = 0; i < numNodes; i++) {
+            node = this.walkPath(root);
+            this.nodes[this.view.getUint32(this.u8BufPos, true)] = node;
+            this.u8BufPos += 4;
+        }
+    }
+
+    decodeStaticStr() {
+        // the string is the pointer and length of utf-8 text in wasm memory
+        ptr = this.view.getUint32(this.u8BufPos, true);
+        len = this.view.getUint32(this.u8BufPos + 4, true);
+        this.u8BufPos += 8;
+        return this.decoder.decode(new DataView(this.view.buffer, ptr, len));
+    }
+
     decodeU32() {
         this.u8BufPos += 4;
         return this.view.getUint32(this.u8BufPos - 4, true);
     }
 
+    AddNames(elements, attributes, namespaces) {
+        // custom names are numbered after the built-in names
+        if (elements) {
+            els.push(...elements.split(" "));
+        }
+        if (attributes) {
+            attrs.push(...attributes.split(" "));
+        }
+        if (namespaces) {
+            nss.push(...namespaces.split(" "));
+        }
+    }
+
     SetNode(id, node) {
         this.nodes[id] = node;
     }
@@ -543,6 +1007,14 @@ export class JsInterpreter {
     }
 }
 
+const nss = [
+    "http://www.w3.org/2000/svg",
+    "http://www.w3.org/1998/Math/MathML",
+    "http://www.w3.org/1999/xlink",
+    "http://www.w3.org/XML/1998/namespace",
+    "http://www.w3.org/2000/xmlns/",
+];
+
 const els = [
     "a",
     "abbr",
@@ -678,6 +1150,12 @@ const els = [
     "video",
     "wbr",
     "xmp",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "search",
 ];
 
 const attrs = [
@@ -834,4 +1312,482 @@ const attrs = [
     "value",
     "width",
     "wrap",
+    "aria-activedescendant",
+    "aria-autocomplete",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colspan",
+    "aria-expanded",
+    "aria-level",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-required",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+    "abbr",
+    "as",
+    "blocking",
+    "exportparts",
+    "fetchpriority",
+    "imagesizes",
+    "imagesrcset",
+    "inert",
+    "is",
+    "itemid",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "nomodule",
+    "nonce",
+    "part",
+    "playsinline",
+    "popover",
+    "popovertarget",
+    "popovertargetaction",
+    "shadowrootmode",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-colindextext",
+    "aria-rowindextext",
+];
+
+const styles = [
+    "align-content",
+    "align-items",
+    "align-self",
+    "animation",
+    "animation-delay",
+    "animation-duration",
+    "animation-name",
+    "animation-play-state",
+    "aspect-ratio",
+    "backdrop-filter",
+    "background",
+    "background-color",
+    "background-image",
+    "background-position",
+    "background-repeat",
+    "background-size",
+    "border",
+    "border-bottom",
+    "border-color",
+    "border-left",
+    "border-radius",
+    "border-right",
+    "border-style",
+    "border-top",
+    "border-width",
+    "bottom",
+    "box-shadow",
+    "box-sizing",
+    "caret-color",
+    "clip-path",
+    "color",
+    "column-gap",
+    "content",
+    "cursor",
+    "display",
+    "fill",
+    "filter",
+    "flex",
+    "flex-basis",
+    "flex-direction",
+    "flex-grow",
+    "flex-shrink",
+    "flex-wrap",
+    "font",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-weight",
+    "gap",
+    "grid-area",
+    "grid-column",
+    "grid-row",
+    "grid-template-areas",
+    "grid-template-columns",
+    "grid-template-rows",
+    "height",
+    "inset",
+    "justify-content",
+    "justify-items",
+    "justify-self",
+    "left",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "mix-blend-mode",
+    "object-fit",
+    "object-position",
+    "opacity",
+    "order",
+    "outline",
+    "outline-offset",
+    "overflow",
+    "overflow-wrap",
+    "overflow-x",
+    "overflow-y",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "pointer-events",
+    "position",
+    "resize",
+    "right",
+    "row-gap",
+    "scroll-behavior",
+    "stroke",
+    "stroke-width",
+    "text-align",
+    "text-decoration",
+    "text-overflow",
+    "text-shadow",
+    "text-transform",
+    "top",
+    "transform",
+    "transform-origin",
+    "transition",
+    "transition-delay",
+    "transition-duration",
+    "transition-property",
+    "transition-timing-function",
+    "translate",
+    "rotate",
+    "scale",
+    "user-select",
+    "vertical-align",
+    "visibility",
+    "white-space",
+    "width",
+    "will-change",
+    "word-break",
+    "z-index",
+];
+
+const svgEls = [
+    "svg",
+    "a",
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "foreignObject",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "script",
+    "set",
+    "stop",
+    "style",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "title",
+    "tspan",
+    "use",
+    "view",
+];
+
+const mathEls = [
+    "math",
+    "annotation",
+    "annotation-xml",
+    "maction",
+    "menclose",
+    "merror",
+    "mfrac",
+    "mi",
+    "mmultiscripts",
+    "mn",
+    "mo",
+    "mover",
+    "mpadded",
+    "mphantom",
+    "mprescripts",
+    "mroot",
+    "mrow",
+    "ms",
+    "mspace",
+    "msqrt",
+    "mstyle",
+    "msub",
+    "msubsup",
+    "msup",
+    "mtable",
+    "mtd",
+    "mtext",
+    "mtr",
+    "munder",
+    "munderover",
+    "semantics",
+];
+
+const svgAttrs = [
+    "accumulate",
+    "additive",
+    "alignment-baseline",
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseline-shift",
+    "begin",
+    "bias",
+    "by",
+    "calcMode",
+    "clip",
+    "clip-path",
+    "clip-rule",
+    "clipPathUnits",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "cursor",
+    "cx",
+    "cy",
+    "d",
+    "diffuseConstant",
+    "direction",
+    "display",
+    "divisor",
+    "dominant-baseline",
+    "dur",
+    "dx",
+    "dy",
+    "edgeMode",
+    "elevation",
+    "end",
+    "exponent",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "filterUnits",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "fr",
+    "from",
+    "fx",
+    "fy",
+    "gradientTransform",
+    "gradientUnits",
+    "image-rendering",
+    "in",
+    "in2",
+    "intercept",
+    "k1",
+    "k2",
+    "k3",
+    "k4",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "letter-spacing",
+    "lighting-color",
+    "limitingConeAngle",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "mode",
+    "numOctaves",
+    "offset",
+    "opacity",
+    "operator",
+    "order",
+    "orient",
+    "origin",
+    "overflow",
+    "paint-order",
+    "path",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointer-events",
+    "points",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "r",
+    "radius",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "restart",
+    "result",
+    "rotate",
+    "rx",
+    "ry",
+    "scale",
+    "seed",
+    "shape-rendering",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "text-anchor",
+    "text-decoration",
+    "text-rendering",
+    "textLength",
+    "to",
+    "transform",
+    "transform-origin",
+    "unicode-bidi",
+    "values",
+    "vector-effect",
+    "viewBox",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+    "x",
+    "x1",
+    "x2",
+    "xChannelSelector",
+    "y",
+    "y1",
+    "y2",
+    "yChannelSelector",
+    "z",
 ];
+
+const mathAttrs = [
+    "accent",
+    "accentunder",
+    "columnalign",
+    "columnlines",
+    "columnspacing",
+    "columnspan",
+    "depth",
+    "display",
+    "displaystyle",
+    "encoding",
+    "fence",
+    "frame",
+    "framespacing",
+    "largeop",
+    "linethickness",
+    "lspace",
+    "mathbackground",
+    "mathcolor",
+    "mathsize",
+    "mathvariant",
+    "maxsize",
+    "minsize",
+    "movablelimits",
+    "notation",
+    "rowalign",
+    "rowlines",
+    "rowspacing",
+    "rspace",
+    "scriptlevel",
+    "separator",
+    "stretchy",
+    "symmetric",
+    "voffset",
+];
+
+// the extended attribute table is the svg attributes followed by the mathml attributes
+const extAttrs = [...svgAttrs, ...mathAttrs];
@@ -1,4 +1,4 @@
-let op, len, ns, attr, i, j, value, element, pos, char, numAttributes, endRounded, inptr, metadata, parent, numNodes, node, id, nodes;
+let op, len, ns, attr, i, j, value, element, ptr, pos, end, out, char, numAttributes, endRounded, inptr, metadata, parent, numNodes, node, name, id, nodes;
 
 export function work_last_created() {
     inptr.Work();
@@ -15,11 +15,31 @@ function exOp() {
     switch (op & 0x1F) {
         // first child
         case 0:
-            inptr.l = inptr.l.firstChild;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the last child is used
+            if (op & 0x20) {
+                inptr.l = inptr.l.lastChild;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the nth child is used
+            else if (op & 0x40) {
+                inptr.l = inptr.l.childNodes[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                inptr.l = inptr.l.firstChild;
+            }
             break;
         // next sibling
         case 1:
-            inptr.l = inptr.l.nextSibling;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the previous sibling is used
+            if (op & 0x20) {
+                inptr.l = inptr.l.previousSibling;
+            }
+            else {
+                inptr.l = inptr.l.nextSibling;
+            }
             break;
         // parent
         case 2:
@@ -52,8 +72,14 @@ function exOp() {
             else {
                 parent = inptr.l;
             }
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if the children are popped from the node stack
+            if (op & 0x80) {
+                parent.append(...inptr.k.splice(inptr.k.length - inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
             // the second bool is encoded as op & (1 << 6)
-            if (op & 0x40) {
+            else if (op & 0x40) {
                 parent.appendChild(inptr.n[inptr.v.u32(inptr.u, true)]);
                 inptr.u += 4;
             }
@@ -73,18 +99,26 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.v.u8(inptr.u++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.v.u8(inptr.u++, true)) {
-                        nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
-                        inptr.u += 4;
-                    }
-                    else {
-                        nodes.push(inptr.l);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.k.splice(inptr.k.length - inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                }
+                else {
+                    numNodes = inptr.v.u8(inptr.u++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.v.u8(inptr.u++, true)) {
+                            nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
+                            inptr.u += 4;
+                        }
+                        else {
+                            nodes.push(inptr.l);
+                        }
                     }
                 }
-                parent.replaceWith(...n);
+                parent.replaceWith(...nodes);
             }
             else {
                 // the third bool is encoded as op & (1 << 7)
@@ -109,18 +143,26 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.v.u8(inptr.u++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.v.u8(inptr.u++, true)) {
-                        nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
-                        inptr.u += 4;
-                    }
-                    else {
-                        nodes.push(inptr.l);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.k.splice(inptr.k.length - inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                }
+                else {
+                    numNodes = inptr.v.u8(inptr.u++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.v.u8(inptr.u++, true)) {
+                            nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
+                            inptr.u += 4;
+                        }
+                        else {
+                            nodes.push(inptr.l);
+                        }
                     }
                 }
-                parent.after(...n);
+                parent.after(...nodes);
             } else {
                 // the third bool is encoded as op & (1 << 7)
                 if (op & 0x80) {
@@ -144,18 +186,26 @@ function exOp() {
             }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                numNodes = inptr.v.u8(inptr.u++, true);
-                nodes = [];
-                for (i = 0; i < numNodes; i++) {
-                    if (inptr.v.u8(inptr.u++, true)) {
-                        nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
-                        inptr.u += 4;
-                    }
-                    else {
-                        nodes.push(inptr.l);
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the nodes are popped from the node stack
+                if (op & 0x80) {
+                    nodes = inptr.k.splice(inptr.k.length - inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                }
+                else {
+                    numNodes = inptr.v.u8(inptr.u++, true);
+                    nodes = [];
+                    for (i = 0; i < numNodes; i++) {
+                        if (inptr.v.u8(inptr.u++, true)) {
+                            nodes.push(inptr.n[inptr.v.u32(inptr.u, true)]);
+                            inptr.u += 4;
+                        }
+                        else {
+                            nodes.push(inptr.l);
+                        }
                     }
                 }
-                parent.before(...n);
+                parent.before(...nodes);
             } else {
                 // the third bool is encoded as op & (1 << 7)
                 if (op & 0x80) {
@@ -180,8 +230,15 @@ function exOp() {
             break;
         // create text node
         case 12:
-            inptr.l = document.createTextNode(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
-            inptr.u += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is a static string in wasm memory
+            if (op & 0x40) {
+                inptr.l = document.createTextNode(inptr.decodeStaticStr());
+            }
+            else {
+                inptr.l = document.createTextNode(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
                 inptr.n[inptr.v.u32(inptr.u, true)] = inptr.l;
@@ -201,13 +258,19 @@ function exOp() {
         case 14:
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                id = inptr.v.u32(inptr.u, true);
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
                 inptr.u += 4;
-                inptr.n[id].textContent = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
-                inptr.u += 2;
             }
             else {
-                inptr.l.textContent = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                node = inptr.l;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is a static string in wasm memory
+            if (op & 0x40) {
+                node.textContent = inptr.decodeStaticStr();
+            }
+            else {
+                node.textContent = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
                 inptr.u += 2;
             }
             break;
@@ -224,33 +287,53 @@ function exOp() {
             // the second bool is encoded as op & (1 << 6)
             // first bool encodes if the attribute is a string
             if (op & 0x40) {
-                // the first two lengths
-                i = inptr.v.u32(inptr.u, true);
-                inptr.u += 4;
-                attr = inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF);
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    node.setAttributeNS(inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF0000) >>> 16), attr, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                    attr = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                    ns = inptr.decodeNs();
+                    node.setAttributeNS(ns, attr, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
                     inptr.u += 2;
                 }
                 else {
+                    // the first two lengths
+                    i = inptr.v.u32(inptr.u, true);
+                    inptr.u += 4;
+                    attr = inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF);
                     node.setAttribute(attr, inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF0000) >>> 16));
                 }
             } else {
-                // the first length and attribute id or the attribute id and the first length
-                i = inptr.v.u32(inptr.u, true);
-                // we only read 3 bytes out of the 4
-                inptr.u += 3;
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    ns = inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF);
-                    node.setAttributeNS(ns, attrs[(i & 0xFF0000) >>> 16], inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                    attr = attrs[inptr.v.u8(inptr.u++)];
+                    ns = inptr.decodeNs();
+                    node.setAttributeNS(ns, attr, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
                     inptr.u += 2;
                 }
                 else {
-                    node.setAttribute(attrs[i & 0xFF], inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF00) >>> 8));
+                    // the attribute id and the first length
+                    i = inptr.v.u32(inptr.u, true);
+                    if ((i & 0xFF) === 252) {
+                        // the attribute is in the extended table
+                        // we use all 4 bytes of i just read
+                        inptr.u += 4;
+                        node.setAttribute(extAttrs[(i & 0xFF00) >>> 8], inptr.s.substring(inptr.o, inptr.o += i >>> 16));
+                    }
+                    else if ((i & 0xFF) === 251 || (i & 0xFF) === 250) {
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        // we only read 3 bytes out of the 4
+                        inptr.u += 3;
+                        attr = ((i & 0xFF) === 251 ? "data-" : "aria-") + inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF00) >>> 8);
+                        node.setAttribute(attr, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                        inptr.u += 2;
+                    }
+                    else {
+                        // we only read 3 bytes out of the 4
+                        inptr.u += 3;
+                        node.setAttribute(attrs[i & 0xFF], inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF00) >>> 8));
+                    }
                 }
             }
             break;
@@ -270,10 +353,9 @@ function exOp() {
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    i = inptr.v.u32(inptr.u, true);
-                    inptr.u += 4;
-                    attr = inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF);
-                    node.removeAttributeNS(inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF0000) >>> 16), attr);
+                    attr = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                    node.removeAttributeNS(inptr.decodeNs(), attr);
                 } else {
                     node.removeAttribute(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
                     inptr.u += 2;
@@ -282,14 +364,20 @@ function exOp() {
                 // the third bool is encoded as op & (1 << 7)
                 // second bool encodes if the attribute has a namespace
                 if (op & 0x80) {
-                    i = inptr.v.u32(inptr.u, true);
-                    // we only read 3 bytes out of the 4
-                    inptr.u += 3;
-                    attr = attrs[i & 0xFF];
-                    node.removeAttributeNS(inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF00) >>> 8), attr);
+                    attr = attrs[inptr.v.u8(inptr.u++)];
+                    node.removeAttributeNS(inptr.decodeNs(), attr);
                 }
                 else {
-                    node.removeAttribute(attrs[inptr.v.u8(inptr.u++)]);
+                    attr = inptr.v.u8(inptr.u++);
+                    if (attr === 251 || attr === 250) {
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        node.removeAttribute((attr === 251 ? "data-" : "aria-") + inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                        inptr.u += 2;
+                    }
+                    else {
+                        // the attribute is either in the extended table or the built-in table
+                        node.removeAttribute(attr === 252 ? extAttrs[inptr.v.u8(inptr.u++)] : attrs[attr]);
+                    }
                 }
             }
             break;
@@ -303,9 +391,24 @@ function exOp() {
             else {
                 node = inptr.l;
             }
-            i = inptr.v.u32(inptr.u, true);
-            inptr.u += 4;
-            node.style.setProperty(inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF), inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF0000) >>> 16));
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is a string
+            if (op & 0x40) {
+                i = inptr.v.u32(inptr.u, true);
+                inptr.u += 4;
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the property is important
+                node.style.setProperty(inptr.s.substring(inptr.o, inptr.o += i & 0xFFFF), inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF0000) >>> 16), op & 0x80 ? "important" : "");
+            }
+            else {
+                // the property id and the value length
+                i = inptr.v.u32(inptr.u, true);
+                // we only read 3 bytes out of the 4
+                inptr.u += 3;
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the property is important
+                node.style.setProperty(styles[i & 0xFF], inptr.s.substring(inptr.o, inptr.o += (i & 0xFFFF00) >>> 8), op & 0x80 ? "important" : "");
+            }
             break;
         // remove style
         case 18:
@@ -317,18 +420,27 @@ function exOp() {
             else {
                 node = inptr.l;
             }
-            node.style.removeProperty(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
-            inptr.u += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is a string
+            if (op & 0x40) {
+                node.style.removeProperty(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
+            else {
+                node.style.removeProperty(styles[inptr.v.u8(inptr.u++)]);
+            }
             break;
         // clone node
         case 19:
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if the clone is shallow
             // the first bool is encoded as op & (1 << 5)
             if (op & 0x20) {
-                inptr.l = inptr.n[inptr.v.u32(inptr.u, true)].cloneNode(true);
+                inptr.l = inptr.n[inptr.v.u32(inptr.u, true)].cloneNode(!(op & 0x80));
                 inptr.u += 4;
             }
             else {
-                inptr.l = inptr.l.cloneNode(true);
+                inptr.l = inptr.l.cloneNode(!(op & 0x80));
             }
             // the second bool is encoded as op & (1 << 6)
             if (op & 0x40) {
@@ -336,6 +448,233 @@ function exOp() {
                 inptr.u += 4;
             }
             break;
+        // set styles
+        case 21:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            numAttributes = inptr.v.u8(inptr.u++);
+            for (i = 0; i < numAttributes; i++) {
+                j = inptr.v.u8(inptr.u++);
+                if (j === 255) {
+                    // the property is encoded as a string
+                    name = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                }
+                else {
+                    name = styles[j];
+                }
+                node.style.setProperty(name, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
+            break;
+        // set css text
+        case 22:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            node.style.cssText = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+            inptr.u += 2;
+            break;
+        // toggle attribute
+        case 23:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            attr = inptr.decodeAttribute();
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the attribute is toggled
+            if (op & 0x40) {
+                value = ns === null ? !node.hasAttribute(attr) : !node.hasAttributeNS(ns, attr);
+            }
+            else {
+                // the third bool is encoded as op & (1 << 7)
+                // third bool encodes if the attribute is added or removed
+                value = op & 0x80;
+            }
+            if (value) {
+                if (ns === null) {
+                    node.setAttribute(attr, "");
+                }
+                else {
+                    node.setAttributeNS(ns, attr, "");
+                }
+            }
+            else if (ns === null) {
+                node.removeAttribute(attr);
+            }
+            else {
+                node.removeAttributeNS(ns, attr);
+            }
+            break;
+        // set attributes
+        case 24:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            numAttributes = inptr.v.u8(inptr.u++);
+            for (i = 0; i < numAttributes; i++) {
+                name = inptr.decodeAttribute();
+                value = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                inptr.u += 2;
+                if (ns === null) {
+                    node.setAttribute(name, value);
+                }
+                else {
+                    node.setAttributeNS(ns, name, value);
+                }
+            }
+            break;
+        // set attribute many
+        case 25:
+            name = inptr.decodeAttribute();
+            numNodes = inptr.v.u16(inptr.u, true);
+            inptr.u += 2;
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if every node gets the same value
+            if (op & 0x20) {
+                value = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                inptr.u += 2;
+            }
+            for (i = 0; i < numNodes; i++) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+                if (!(op & 0x20)) {
+                    value = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                    inptr.u += 2;
+                }
+                if (ns === null) {
+                    node.setAttribute(name, value);
+                }
+                else {
+                    node.setAttributeNS(ns, name, value);
+                }
+            }
+            break;
+        // dataset
+        case 26:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            name = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+            inptr.u += 2;
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the property is removed
+            if (op & 0x40) {
+                delete node.dataset[name];
+            }
+            else {
+                node.dataset[name] = inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true));
+                inptr.u += 2;
+            }
+            break;
+        // edit text
+        case 27:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                node = inptr.n[inptr.v.u32(inptr.u, true)];
+                inptr.u += 4;
+            }
+            else {
+                node = inptr.l;
+            }
+            // the second bool is encoded as op & (1 << 6)
+            // second bool encodes if the text is inserted at an offset
+            if (op & 0x40) {
+                i = inptr.v.u32(inptr.u, true);
+                inptr.u += 4;
+                node.insertData(i, inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
+            // the third bool is encoded as op & (1 << 7)
+            // third bool encodes if text is deleted
+            else if (op & 0x80) {
+                node.deleteData(inptr.v.u32(inptr.u, true), inptr.v.u32(inptr.u + 4, true));
+                inptr.u += 8;
+            }
+            else {
+                node.appendData(inptr.s.substring(inptr.o, inptr.o += inptr.v.u16(inptr.u, true)));
+                inptr.u += 2;
+            }
+            break;
+        // push root
+        case 28:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                inptr.k.push(inptr.n[inptr.v.u32(inptr.u, true)]);
+                inptr.u += 4;
+            }
+            else {
+                inptr.k.push(inptr.l);
+            }
+            break;
+        // navigate
+        case 29:
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the nodes at many paths are stored with ids
+            if (op & 0x20) {
+                // the second bool is encoded as op & (1 << 6)
+                if (op & 0x40) {
+                    node = inptr.n[inptr.v.u32(inptr.u, true)];
+                    inptr.u += 4;
+                }
+                else {
+                    node = inptr.l;
+                }
+                inptr.storePaths(node);
+            }
+            else {
+                inptr.l = inptr.walkPath(inptr.l);
+            }
+            break;
+        // cursor
+        case 30:
+            // the first bool is encoded as op & (1 << 5)
+            // first bool encodes if the last node is restored instead of saved
+            if (op & 0x20) {
+                inptr.l = inptr.c.pop();
+            }
+            else {
+                inptr.c.push(inptr.l);
+            }
+            break;
+        // clone node with ids
+        case 31:
+            // the first bool is encoded as op & (1 << 5)
+            if (op & 0x20) {
+                inptr.l = inptr.n[inptr.v.u32(inptr.u, true)].cloneNode(true);
+                inptr.u += 4;
+            }
+            else {
+                inptr.l = inptr.l.cloneNode(true);
+            }
+            inptr.storePaths(inptr.l);
+            break;
         default:
             break;
     }
@@ -346,6 +685,8 @@ export class JsInterpreter {
         this.l;
         this.n = [];
         this.p = [];
+        this.k = [];
+        this.c = [];
         this.UpdateMemory(mem);
         this.lp;
         this.ls;
@@ -382,9 +723,29 @@ export class JsInterpreter {
             if (metadata & 0x02) {
                 this.ls = this.v.u32(this.sp, true);
             }
+            // utf-16 strings are already code units, so they can be turned into a string without a TextDecoder
+            if (metadata & 0x10) {
+                pos = this.ls;
+                end = pos + len;
+                this.s = "";
+                if (pos & 1) {
+                    // a Uint16Array must be aligned to two bytes
+                    while (pos < end) {
+                        this.s += String.fromCharCode(this.v.u16(pos, true));
+                        pos += 2;
+                    }
+                }
+                else {
+                    out = new Uint16Array(this.v.buffer, pos, len >>> 1);
+                    // passing too many arguments to fromCharCode at once overflows the stack
+                    for (i = 0; i < out.length; i += 4096) {
+                        this.s += String.fromCharCode.apply(null, out.subarray(i, i + 4096));
+                    }
+                }
+            }
             // for small strings decoding them in javascript to avoid the overhead of native calls is faster
             // the fourth boolean contains information about whether the string is all ascii or utf8 and small
-            if (metadata & 0x08) {
+            else if (metadata & 0x08) {
                 pos = this.ls;
                 this.s = "";
                 endRounded = pos + ((len / 4) | 0) * 4;
@@ -436,25 +797,32 @@ export class JsInterpreter {
         element = j & 0xFF;
         switch (element) {
             case 255:
-                // the element is encoded as an enum and the namespace is encoded as a string
-                // we use all 4 bytes of i just read
-                this.u += 4;
-                element = document.createElement(els[(j & 0xFF00) >>> 8], this.s.substring(this.o, this.o += (j & 0xFFFF0000) >>> 16));
-                return element;
+                // the element is encoded as an enum followed by the namespace
+                // we use 2 bytes of j just read
+                this.u += 2;
+                element = els[(j & 0xFF00) >>> 8];
+                return document.createElementNS(this.decodeNs(), element);
             case 254:
                 // the element is encoded as a string
                 // we use 3 bytes of i just read
                 this.u += 3;
                 element = document.createElement(this.s.substring(this.o, this.o += (j & 0xFFFF00) >>> 8));
                 return element;
+            case 252:
+                // the element is in the extended table and its namespace is implied by the table
+                // we use 2 bytes of j just read
+                this.u += 2;
+                element = (j & 0xFF00) >>> 8;
+                if (element < svgEls.length) {
+                    return document.createElementNS(nss[0], svgEls[element]);
+                }
+                return document.createElementNS(nss[1], mathEls[element - svgEls.length]);
             case 253:
-                // the element and namespace are encoded as strings
-                // we use 3 bytes of i just read
+                // the element is encoded as a string followed by the namespace
+                // we use 3 bytes of j just read
                 this.u += 3;
                 element = this.s.substring(this.o, this.o += (j & 0xFFFF00) >>> 8);
-                element = document.createElementNS(this.s.substring(this.o, this.o += this.v.u16(this.u, true)), element);
-                this.u += 2;
-                return element;
+                return document.createElementNS(this.decodeNs(), element);
             default:
                 this.u++;
                 // the element is encoded as an enum
@@ -488,11 +856,13 @@ export class JsInterpreter {
                 attr = j & 0xFF;
                 switch (attr) {
                     case 255:
-                        // the attribute is encoded as an enum and the namespace is encoded as a string
-                        // we use all 4 bytes of j just read
-                        this.u += 4;
-                        attr = attrs[this.v.u8((j & 0xFF00) >>> 8)];
-                        parent_element.setAttributeNS(this.s.substring(this.o, this.o += (j & 0xFFFF0000) >>> 16), attr);
+                        // the attribute is encoded as an enum followed by the namespace
+                        // we use 2 bytes of j just read
+                        this.u += 2;
+                        attr = attrs[(j & 0xFF00) >>> 8];
+                        ns = this.decodeNs();
+                        parent_element.setAttributeNS(ns, attr, this.s.substring(this.o, this.o += this.v.u16(this.u, true)));
+                        this.u += 2;
                         break;
                     case 254:
                         // the attribute is encoded as a string
@@ -503,15 +873,29 @@ export class JsInterpreter {
                         attr = this.s.substring(this.o, this.o += j & 0xFFFF);
                         parent_element.setAttribute(attr, this.s.substring(this.o, this.o += (j & 0xFFFF0000) >>> 16));
                         break;
+                    case 252:
+                        // the attribute is in the extended table
+                        // we use all 4 bytes of j just read
+                        this.u += 4;
+                        parent_element.setAttribute(extAttrs[(j & 0xFF00) >>> 8], this.s.substring(this.o, this.o += j >>> 16));
+                        break;
+                    case 251:
+                    case 250:
+                        // the attribute is a data- or aria- prefix followed by the suffix
+                        // we use 3 bytes of j just read
+                        this.u += 3;
+                        attr = (attr === 251 ? "data-" : "aria-") + this.s.substring(this.o, this.o += (j & 0xFFFF00) >>> 8);
+                        parent_element.setAttribute(attr, this.s.substring(this.o, this.o += this.v.u16(this.u, true)));
+                        this.u += 2;
+                        break;
                     case 253:
-                        // the attribute and namespace are encoded as strings
+                        // the attribute is encoded as a string followed by the namespace
                         // we use 3 bytes of j just read
                         this.u += 3;
                         attr = this.s.substring(this.o, this.o += (j & 0xFFFF00) >>> 8);
-                        j = this.v.u32(this.u, true);
-                        this.u += 4;
-                        ns = this.s.substring(this.o, this.o += j & 0xFFFF);
-                        value = this.s.substring(this.o, this.o += (j & 0xFFFF0000) >>> 16);
+                        ns = this.decodeNs();
+                        value = this.s.substring(this.o, this.o += this.v.u16(this.u, true));
+                        this.u += 2;
                         parent_element.setAttributeNS(ns, attr, value);
                         break;
                     default:
@@ -531,11 +915,91 @@ export class JsInterpreter {
         }
     }
 
+    decodeNs() {
+        // the namespace is either an index into the namespace table or 255 followed by the length of the namespace string
+        const code = this.v.u8(this.u++);
+        if (code === 255) {
+            len = this.v.u16(this.u, true);
+            this.u += 2;
+            return this.s.substring(this.o, this.o += len);
+        }
+        return nss[code];
+    }
+
+    decodeAttribute() {
+        // the attribute is encoded with a u8 discriminant, the namespace is stored in ns
+        ns = null;
+        j = this.v.u8(this.u++);
+        switch (j) {
+            case 255:
+                attr = attrs[this.v.u8(this.u++)];
+                ns = this.decodeNs();
+                return attr;
+            case 254:
+                this.u += 2;
+                return this.s.substring(this.o, this.o += this.v.u16(this.u - 2, true));
+            case 253:
+                this.u += 2;
+                attr = this.s.substring(this.o, this.o += this.v.u16(this.u - 2, true));
+                ns = this.decodeNs();
+                return attr;
+            case 252:
+                return extAttrs[this.v.u8(this.u++)];
+            case 251:
+            case 250:
+                this.u += 2;
+                return (j === 251 ? "data-" : "aria-") + this.s.substring(this.o, this.o += this.v.u16(this.u - 2, true));
+            default:
+                return attrs[j];
+        }
+    }
+
+    walkPath(node) {
+        // the path is a u8 length followed by the index of the child at each step
+        end = this.u + 1 + this.v.u8(this.u);
+        for (this.u++; this.u < end; this.u++) {
+            node = node.childNodes[this.v.u8(this.u)];
+        }
+        return node;
+    }
+
+    storePaths(root) {
+        // a u16 number of paths each followed by the id to store the node at the path with
+        numNodes = this.v.u16(this.u, true);
+        this.u += 2;
+        for (i = 0; i < numNodes; i++) {
+            node = this.walkPath(root);
+            this.n[this.v.u32(this.u, true)] = node;
+            this.u += 4;
+        }
+    }
+
+    decodeStaticStr() {
+        // the string is the pointer and length of utf-8 text in wasm memory
+        ptr = this.v.u32(this.u, true);
+        len = this.v.u32(this.u + 4, true);
+        this.u += 8;
+        return this.d.decode(new DataView(this.v.buffer, ptr, len));
+    }
+
     decodeU32() {
         this.u += 4;
         return this.v.u32(this.u - 4, true);
     }
 
+    AddNames(elements, attributes, namespaces) {
+        // custom names are numbered after the built-in names
+        if (elements) {
+            els.push(...elements.split(" "));
+        }
+        if (attributes) {
+            attrs.push(...attributes.split(" "));
+        }
+        if (namespaces) {
+            nss.push(...namespaces.split(" "));
+        }
+    }
+
     SetNode(id, node) {
         this.n[id] = node;
     }
@@ -545,6 +1009,14 @@ export class JsInterpreter {
     }
 }
 
+const nss = [
+    "http://www.w3.org/2000/svg",
+    "http://www.w3.org/1998/Math/MathML",
+    "http://www.w3.org/1999/xlink",
+    "http://www.w3.org/XML/1998/namespace",
+    "http://www.w3.org/2000/xmlns/",
+];
+
 const els = [
     "a",
     "abbr",
@@ -680,6 +1152,12 @@ const els = [
     "video",
     "wbr",
     "xmp",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "search",
 ];
 
 const attrs = [
@@ -836,4 +1314,482 @@ const attrs = [
     "value",
     "width",
     "wrap",
+    "aria-activedescendant",
+    "aria-autocomplete",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colspan",
+    "aria-expanded",
+    "aria-level",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-required",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+    "abbr",
+    "as",
+    "blocking",
+    "exportparts",
+    "fetchpriority",
+    "imagesizes",
+    "imagesrcset",
+    "inert",
+    "is",
+    "itemid",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "nomodule",
+    "nonce",
+    "part",
+    "playsinline",
+    "popover",
+    "popovertarget",
+    "popovertargetaction",
+    "shadowrootmode",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-colindextext",
+    "aria-rowindextext",
+];
+
+const styles = [
+    "align-content",
+    "align-items",
+    "align-self",
+    "animation",
+    "animation-delay",
+    "animation-duration",
+    "animation-name",
+    "animation-play-state",
+    "aspect-ratio",
+    "backdrop-filter",
+    "background",
+    "background-color",
+    "background-image",
+    "background-position",
+    "background-repeat",
+    "background-size",
+    "border",
+    "border-bottom",
+    "border-color",
+    "border-left",
+    "border-radius",
+    "border-right",
+    "border-style",
+    "border-top",
+    "border-width",
+    "bottom",
+    "box-shadow",
+    "box-sizing",
+    "caret-color",
+    "clip-path",
+    "color",
+    "column-gap",
+    "content",
+    "cursor",
+    "display",
+    "fill",
+    "filter",
+    "flex",
+    "flex-basis",
+    "flex-direction",
+    "flex-grow",
+    "flex-shrink",
+    "flex-wrap",
+    "font",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-weight",
+    "gap",
+    "grid-area",
+    "grid-column",
+    "grid-row",
+    "grid-template-areas",
+    "grid-template-columns",
+    "grid-template-rows",
+    "height",
+    "inset",
+    "justify-content",
+    "justify-items",
+    "justify-self",
+    "left",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "mix-blend-mode",
+    "object-fit",
+    "object-position",
+    "opacity",
+    "order",
+    "outline",
+    "outline-offset",
+    "overflow",
+    "overflow-wrap",
+    "overflow-x",
+    "overflow-y",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "pointer-events",
+    "position",
+    "resize",
+    "right",
+    "row-gap",
+    "scroll-behavior",
+    "stroke",
+    "stroke-width",
+    "text-align",
+    "text-decoration",
+    "text-overflow",
+    "text-shadow",
+    "text-transform",
+    "top",
+    "transform",
+    "transform-origin",
+    "transition",
+    "transition-delay",
+    "transition-duration",
+    "transition-property",
+    "transition-timing-function",
+    "translate",
+    "rotate",
+    "scale",
+    "user-select",
+    "vertical-align",
+    "visibility",
+    "white-space",
+    "width",
+    "will-change",
+    "word-break",
+    "z-index",
+];
+
+const svgEls = [
+    "svg",
+    "a",
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "foreignObject",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "script",
+    "set",
+    "stop",
+    "style",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "title",
+    "tspan",
+    "use",
+    "view",
+];
+
+const mathEls = [
+    "math",
+    "annotation",
+    "annotation-xml",
+    "maction",
+    "menclose",
+    "merror",
+    "mfrac",
+    "mi",
+    "mmultiscripts",
+    "mn",
+    "mo",
+    "mover",
+    "mpadded",
+    "mphantom",
+    "mprescripts",
+    "mroot",
+    "mrow",
+    "ms",
+    "mspace",
+    "msqrt",
+    "mstyle",
+    "msub",
+    "msubsup",
+    "msup",
+    "mtable",
+    "mtd",
+    "mtext",
+    "mtr",
+    "munder",
+    "munderover",
+    "semantics",
+];
+
+const svgAttrs = [
+    "accumulate",
+    "additive",
+    "alignment-baseline",
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseline-shift",
+    "begin",
+    "bias",
+    "by",
+    "calcMode",
+    "clip",
+    "clip-path",
+    "clip-rule",
+    "clipPathUnits",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "cursor",
+    "cx",
+    "cy",
+    "d",
+    "diffuseConstant",
+    "direction",
+    "display",
+    "divisor",
+    "dominant-baseline",
+    "dur",
+    "dx",
+    "dy",
+    "edgeMode",
+    "elevation",
+    "end",
+    "exponent",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "filterUnits",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "fr",
+    "from",
+    "fx",
+    "fy",
+    "gradientTransform",
+    "gradientUnits",
+    "image-rendering",
+    "in",
+    "in2",
+    "intercept",
+    "k1",
+    "k2",
+    "k3",
+    "k4",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "letter-spacing",
+    "lighting-color",
+    "limitingConeAngle",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "mode",
+    "numOctaves",
+    "offset",
+    "opacity",
+    "operator",
+    "order",
+    "orient",
+    "origin",
+    "overflow",
+    "paint-order",
+    "path",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointer-events",
+    "points",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "r",
+    "radius",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "restart",
+    "result",
+    "rotate",
+    "rx",
+    "ry",
+    "scale",
+    "seed",
+    "shape-rendering",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "text-anchor",
+    "text-decoration",
+    "text-rendering",
+    "textLength",
+    "to",
+    "transform",
+    "transform-origin",
+    "unicode-bidi",
+    "values",
+    "vector-effect",
+    "viewBox",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+    "x",
+    "x1",
+    "x2",
+    "xChannelSelector",
+    "y",
+    "y1",
+    "y2",
+    "yChannelSelector",
+    "z",
 ];
+
+const mathAttrs = [
+    "accent",
+    "accentunder",
+    "columnalign",
+    "columnlines",
+    "columnspacing",
+    "columnspan",
+    "depth",
+    "display",
+    "displaystyle",
+    "encoding",
+    "fence",
+    "frame",
+    "framespacing",
+    "largeop",
+    "linethickness",
+    "lspace",
+    "mathbackground",
+    "mathcolor",
+    "mathsize",
+    "mathvariant",
+    "maxsize",
+    "minsize",
+    "movablelimits",
+    "notation",
+    "rowalign",
+    "rowlines",
+    "rowspacing",
+    "rspace",
+    "scriptlevel",
+    "separator",
+    "stretchy",
+    "symmetric",
+    "voffset",
+];
+
+// the extended attribute table is the svg attributes followed by the mathml attributes
+const extAttrs = [...svgAttrs, ...mathAttrs];
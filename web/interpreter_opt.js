@@ -1 +1 @@
-let e,t,s,r,i,u,a,o,l,h,c,d,b,p,f,m,v,g,k;export function work_last_created(){b.Work()}export function update_last_memory(e){b.UpdateMemory(e)}function y(){switch(e&31){case 0:b.l=b.l.firstChild;break;case 1:b.l=b.l.nextSibling;break;case 2:b.l=b.l.parentNode;break;case 3:b.n[b.v.u32(b.u,true)]=b.l;b.u+=4;break;case 4:b.l=b.n[b.v.u32(b.u,true)];b.u+=4;break;case 5:return true;case 6:b.l=b.createFullElement();break;case 7:if(e&32){f=b.n[b.v.u32(b.u,true)];b.u+=4}else{f=b.l}if(e&64){f.appendChild(b.n[b.v.u32(b.u,true)]);b.u+=4}else{f.appendChild(b.l)}break;case 8:if(e&64){f=b.n[b.v.u32(b.u,true)];b.u+=4}else{f=b.l}if(e&32){m=b.v.u8(b.u++,true);k=[];for(i=0;i<m;i++){if(b.v.u8(b.u++,true)){k.push(b.n[b.v.u32(b.u,true)]);b.u+=4}else{k.push(b.l)}}f.replaceWith(...n)}else{if(e&128){f.replaceWith(b.n[b.v.u32(b.u,true)]);b.u+=4}else{f.replaceWith(b.l)}}break;case 9:if(e&64){f=b.n[b.v.u32(b.u,true)];b.u+=4}else{f=b.l}if(e&32){m=b.v.u8(b.u++,true);k=[];for(i=0;i<m;i++){if(b.v.u8(b.u++,true)){k.push(b.n[b.v.u32(b.u,true)]);b.u+=4}else{k.push(b.l)}}f.after(...n)}else{if(e&128){f.after(b.n[b.v.u32(b.u,true)]);b.u+=4}else{f.after(b.l)}}break;case 10:if(e&64){f=b.n[b.v.u32(b.u,true)];b.u+=4}else{f=b.l}if(e&32){m=b.v.u8(b.u++,true);k=[];for(i=0;i<m;i++){if(b.v.u8(b.u++,true)){k.push(b.n[b.v.u32(b.u,true)]);b.u+=4}else{k.push(b.l)}}f.before(...n)}else{if(e&128){f.before(b.n[b.v.u32(b.u,true)]);b.u+=4}else{f.before(b.l)}}break;case 11:if(e&32){b.n[b.v.u32(b.u,true)].remove();b.u+=4}else{b.l.remove()}break;case 12:b.l=document.createTextNode(b.s.substring(b.o,b.o+=b.v.u16(b.u,true)));b.u+=2;if(e&32){b.n[b.v.u32(b.u,true)]=b.l;b.u+=4}break;case 13:b.l=b.createElement();if(e&32){b.n[b.v.u32(b.u,true)]=b.l;b.u+=4}break;case 14:if(e&32){g=b.v.u32(b.u,true);b.u+=4;b.n[g].textContent=b.s.substring(b.o,b.o+=b.v.u16(b.u,true));b.u+=2}else{b.l.textContent=b.s.substring(b.o,b.o+=b.v.u16(b.u,true));b.u+=2}break;case 15:if(e&32){v=b.n[b.v.u32(b.u,true)];b.u+=4}else{v=b.l}if(e&64){i=b.v.u32(b.u,true);b.u+=4;r=b.s.substring(b.o,b.o+=i&65535);if(e&128){v.setAttributeNS(b.s.substring(b.o,b.o+=(i&4294901760)>>>16),r,b.s.substring(b.o,b.o+=b.v.u16(b.u,true)));b.u+=2}else{v.setAttribute(r,b.s.substring(b.o,b.o+=(i&4294901760)>>>16))}}else{i=b.v.u32(b.u,true);b.u+=3;if(e&128){s=b.s.substring(b.o,b.o+=i&65535);v.setAttributeNS(s,x[(i&16711680)>>>16],b.s.substring(b.o,b.o+=b.v.u16(b.u,true)));b.u+=2}else{v.setAttribute(x[i&255],b.s.substring(b.o,b.o+=(i&16776960)>>>8))}}break;case 16:if(e&32){v=b.n[b.v.u32(b.u,true)];b.u+=4}else{v=b.l}if(e&64){if(e&128){i=b.v.u32(b.u,true);b.u+=4;r=b.s.substring(b.o,b.o+=i&65535);v.removeAttributeNS(b.s.substring(b.o,b.o+=(i&4294901760)>>>16),r)}else{v.removeAttribute(b.s.substring(b.o,b.o+=b.v.u16(b.u,true)));b.u+=2}}else{if(e&128){i=b.v.u32(b.u,true);b.u+=3;r=x[i&255];v.removeAttributeNS(b.s.substring(b.o,b.o+=(i&16776960)>>>8),r)}else{v.removeAttribute(x[b.v.u8(b.u++)])}}break;case 17:if(e&32){v=b.n[b.v.u32(b.u,true)];b.u+=4}else{v=b.l}i=b.v.u32(b.u,true);b.u+=4;v.style.setProperty(b.s.substring(b.o,b.o+=i&65535),b.s.substring(b.o,b.o+=(i&4294901760)>>>16));break;case 18:if(e&32){v=b.n[b.v.u32(b.u,true)];b.u+=4}else{v=b.l}v.style.removeProperty(b.s.substring(b.o,b.o+=b.v.u16(b.u,true)));b.u+=2;break;case 19:if(e&32){b.l=b.n[b.v.u32(b.u,true)].cloneNode(true);b.u+=4}else{b.l=b.l.cloneNode(true)}if(e&64){b.n[b.v.u32(b.u,true)]=b.l;b.u+=4}break;default:break}}export class JsInterpreter{constructor(e,t,s,r,i){this.l;this.n=[];this.p=[];this.UpdateMemory(e);this.lp;this.ls;this.m=t;this.pt=s;this.sp=r;this.sl=i;this.s="";this.o=0;this.d=new TextDecoder;this.i=1;b=this}NeedsMemory(){return this.v.buffer.byteLength===0}UpdateMemory(e){this.v=new DataView(e.buffer);this.v.u32=this.v.getUint32;this.v.u16=this.v.getUint16;this.v.u8=this.v.getUint8}Work(){p=this.v.u8(this.m);if(p&1){this.lp=this.v.u32(this.pt,true)}this.u=this.lp;if(p&4){t=this.v.u32(this.sl,true);if(p&2){this.ls=this.v.u32(this.sp,true)}if(p&8){l=this.ls;this.s="";d=l+(t/4|0)*4;while(l<d){h=this.v.u32(l);this.s+=String.fromCharCode(h>>24,(h&16711680)>>16,(h&65280)>>8,h&255);l+=4}switch(this.ls+t-l){case 3:h=this.v.u32(l);this.s+=String.fromCharCode(h>>24,(h&16711680)>>16,(h&65280)>>8);break;case 2:h=this.v.u16(l);this.s+=String.fromCharCode(h>>8,h&255);break;case 1:this.s+=String.fromCharCode(this.v.u8(l));break;case 0:break}}else{this.s=this.d.decode(new DataView(this.v.buffer,this.ls,t))}this.o=0}for(;;){e=this.v.u32(this.u,true);this.u+=4;if(y())return;e>>>=8;if(y())return;e>>>=8;if(y())return;e>>>=8;if(y())return}}createElement(){u=this.v.u32(this.u,true);o=u&255;switch(o){case 255:this.u+=4;o=document.createElement(w[(u&65280)>>>8],this.s.substring(this.o,this.o+=(u&4294901760)>>>16));return o;case 254:this.u+=3;o=document.createElement(this.s.substring(this.o,this.o+=(u&16776960)>>>8));return o;case 253:this.u+=3;o=this.s.substring(this.o,this.o+=(u&16776960)>>>8);o=document.createElementNS(this.s.substring(this.o,this.o+=this.v.u16(this.u,true)),o);this.u+=2;return o;default:this.u++;return document.createElement(w[o])}}createFullElement(){let e;u=this.v.u8(this.u++);if(u&1){e=this.v.u32(this.u,true);this.u+=4}if(u&2){v=document.createTextNode(this.s.substring(this.o,this.o+=this.v.u16(this.u,true)));this.u+=2;if(e!==null){this.n[e]=v}return v}else{const t=this.createElement();u=this.v.u16(this.u,true);this.u+=2;c=u&255;const o=(u&65280)>>>8;for(i=0;i<c;i++){u=this.v.u32(this.u,true);r=u&255;switch(r){case 255:this.u+=4;r=x[this.v.u8((u&65280)>>>8)];t.setAttributeNS(this.s.substring(this.o,this.o+=(u&4294901760)>>>16),r);break;case 254:this.u++;u=this.v.u32(this.u,true);this.u+=4;r=this.s.substring(this.o,this.o+=u&65535);t.setAttribute(r,this.s.substring(this.o,this.o+=(u&4294901760)>>>16));break;case 253:this.u+=3;r=this.s.substring(this.o,this.o+=(u&16776960)>>>8);u=this.v.u32(this.u,true);this.u+=4;s=this.s.substring(this.o,this.o+=u&65535);a=this.s.substring(this.o,this.o+=(u&4294901760)>>>16);t.setAttributeNS(s,r,a);break;default:this.u+=3;t.setAttribute(x[r],this.s.substring(this.o,this.o+=(u&16776960)>>>8));break}}for(let l=0;l<o;l++){t.appendChild(this.createFullElement())}if(e!==null){this.n[e]=t}return t}}decodeU32(){this.u+=4;return this.v.u32(this.u-4,true)}SetNode(e,t){this.n[e]=t}GetNode(e){return this.n[e]}}const w=["a","abbr","acronym","address","applet","area","article","aside","audio","b","base","bdi","bdo","bgsound","big","blink","blockquote","body","br","button","canvas","caption","center","cite","code","col","colgroup","content","data","datalist","dd","del","details","dfn","dialog","dir","div","dl","dt","em","embed","fieldset","figcaption","figure","font","footer","form","frame","frameset","h1","head","header","hgroup","hr","html","i","iframe","image","img","input","ins","kbd","keygen","label","legend","li","link","main","map","mark","marquee","menu","menuitem","meta","meter","nav","nobr","noembed","noframes","noscript","object","ol","optgroup","option","output","p","param","picture","plaintext","portal","pre","progress","q","rb","rp","rt","rtc","ruby","s","samp","script","section","select","shadow","slot","small","source","spacer","span","strike","strong","style","sub","summary","sup","table","tbody","td","template","textarea","tfoot","th","thead","time","title","tr","track","tt","u","ul","var","video","wbr","xmp"];const x=["accept-charset","accept","accesskey","action","align","allow","alt","aria-atomic","aria-busy","aria-controls","aria-current","aria-describedby","aria-description","aria-details","aria-disabled","aria-dropeffect","aria-errormessage","aria-flowto","aria-grabbed","aria-haspopup","aria-hidden","aria-invalid","aria-keyshortcuts","aria-label","aria-labelledby","aria-live","aria-owns","aria-relevant","aria-roledescription","async","autocapitalize","autocomplete","autofocus","autoplay","background","bgcolor","border","buffered","capture","challenge","charset","checked","cite","class","code","codebase","color","cols","colspan","content","contenteditable","contextmenu","controls","coords","crossorigin","csp","data","datetime","decoding","default","defer","dir","dirname","disabled","download","draggable","enctype","enterkeyhint","for","form","formaction","formenctype","formmethod","formnovalidate","formtarget","headers","height","hidden","high","href","hreflang","http-equiv","icon","id","importance","inputmode","integrity","intrinsicsize","ismap","itemprop","keytype","kind","label","lang","language","list","loading","loop","low","manifest","max","maxlength","media","method","min","minlength","multiple","muted","name","novalidate","open","optimum","pattern","ping","placeholder","poster","preload","radiogroup","readonly","referrerpolicy","rel","required","reversed","role","rows","rowspan","sandbox","scope","scoped","selected","shape","size","sizes","slot","span","spellcheck","src","srcdoc","srclang","srcset","start","step","style","summary","tabindex","target","title","translate","type","usemap","value","width","wrap"];
\ No newline at end of file
+let T=8,ay=`border`,_=251,aL=`font-style`,aY=`scale`,ad=` `,Q=2,aC=`translate`,a3=`important`,M=32,$=250,aD=`width`,R=3,aX=`rotate`,N=64,ai=`content`,K=31,ah=`code`,at=`span`,aA=`height`,X=255,aw=`title`,aS=`stroke`,al=`font`,av=`summary`,aJ=`font-family`,Z=65280,W=16,aG=`display`,ag=`cite`,ap=`label`,a5=null,a$=`path`,aV=`transform`,af=`abbr`,a_=`mask`,an=`frame`,aN=`letter-spacing`,aW=`transform-origin`,a9=4096,ax=`background`,aQ=`overflow`,aT=`stroke-width`,ae=`a`,ak=`dir`,S=128,a6=24,aZ=`visibility`,az=`color`,ac=253,au=`style`,U=65535,ab=254,L=0,am=`form`,ar=`slot`,aa=16711680,aH=`fill`,aB=`pattern`,Y=252,aU=`text-decoration`,P=1,a2=16776960,aO=`opacity`,aR=`pointer-events`,aj=`data`,aq=`script`,a1=`aria-`,aP=`order`,aI=`filter`,aE=`clip-path`,aM=`font-weight`,V=4294901760,a0=`data-`,aK=`font-size`,a4=``,ao=`image`,aF=`cursor`,O=4,a7=DataView,a8=String.fromCharCode;var z=(()=>{switch(a&K){case L:if(a&M){p.l=p.l.lastChild}else if(a&N){p.l=p.l.childNodes[p.v.u32(p.u,!0)];p.u+=O}else{p.l=p.l.firstChild};break;case P:if(a&M){p.l=p.l.previousSibling}else{p.l=p.l.nextSibling};break;case Q:p.l=p.l.parentNode;break;case R:p.n[p.v.u32(p.u,!0)]=p.l;p.u+=O;break;case O:p.l=p.n[p.v.u32(p.u,!0)];p.u+=O;break;case 5:return !0;case 6:p.l=p.createFullElement();break;case 7:if(a&M){r=p.n[p.v.u32(p.u,!0)];p.u+=O}else{r=p.l};if(a&S){r.append(...p.k.splice(p.k.length- p.v.u16(p.u,!0)));p.u+=Q}else if(a&N){r.appendChild(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{r.appendChild(p.l)};break;case T:if(a&N){r=p.n[p.v.u32(p.u,!0)];p.u+=O}else{r=p.l};if(a&M){if(a&S){w=p.k.splice(p.k.length- p.v.u16(p.u,!0));p.u+=Q}else{s=p.v.u8(p.u++,!0);w=[];for(e=L;e<s;e++){if(p.v.u8(p.u++,!0)){w.push(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{w.push(p.l)}}};r.replaceWith(...w)}else{if(a&S){r.replaceWith(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{r.replaceWith(p.l)}};break;case 9:if(a&N){r=p.n[p.v.u32(p.u,!0)];p.u+=O}else{r=p.l};if(a&M){if(a&S){w=p.k.splice(p.k.length- p.v.u16(p.u,!0));p.u+=Q}else{s=p.v.u8(p.u++,!0);w=[];for(e=L;e<s;e++){if(p.v.u8(p.u++,!0)){w.push(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{w.push(p.l)}}};r.after(...w)}else{if(a&S){r.after(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{r.after(p.l)}};break;case 10:if(a&N){r=p.n[p.v.u32(p.u,!0)];p.u+=O}else{r=p.l};if(a&M){if(a&S){w=p.k.splice(p.k.length- p.v.u16(p.u,!0));p.u+=Q}else{s=p.v.u8(p.u++,!0);w=[];for(e=L;e<s;e++){if(p.v.u8(p.u++,!0)){w.push(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{w.push(p.l)}}};r.before(...w)}else{if(a&S){r.before(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{r.before(p.l)}};break;case 11:if(a&M){p.n[p.v.u32(p.u,!0)].remove();p.u+=O}else{p.l.remove()};break;case 12:if(a&N){p.l=document.createTextNode(p.decodeStaticStr())}else{p.l=document.createTextNode(p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q};if(a&M){p.n[p.v.u32(p.u,!0)]=p.l;p.u+=O};break;case 13:p.l=p.createElement();if(a&M){p.n[p.v.u32(p.u,!0)]=p.l;p.u+=O};break;case 14:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){t.textContent=p.decodeStaticStr()}else{t.textContent=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q};break;case 15:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){if(a&S){d=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q;c=p.decodeNs();t.setAttributeNS(c,d,p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else{e=p.v.u32(p.u,!0);p.u+=O;d=p.s.substring(p.o,p.o+=e&U);t.setAttribute(d,p.s.substring(p.o,p.o+=(e&V)>>>W))}}else{if(a&S){d=D[p.v.u8(p.u++)];c=p.decodeNs();t.setAttributeNS(c,d,p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else{e=p.v.u32(p.u,!0);if((e&X)===Y){p.u+=O;t.setAttribute(J[(e&Z)>>>T],p.s.substring(p.o,p.o+=e>>>W))}else if((e&X)===_||(e&X)===$){p.u+=R;d=((e&X)===_?a0:a1)+ p.s.substring(p.o,p.o+=(e&a2)>>>T);t.setAttribute(d,p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else{p.u+=R;t.setAttribute(D[e&X],p.s.substring(p.o,p.o+=(e&a2)>>>T))}}};break;case W:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){if(a&S){d=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q;t.removeAttributeNS(p.decodeNs(),d)}else{t.removeAttribute(p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}}else{if(a&S){d=D[p.v.u8(p.u++)];t.removeAttributeNS(p.decodeNs(),d)}else{d=p.v.u8(p.u++);if(d===_||d===$){t.removeAttribute((d===_?a0:a1)+ p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else{t.removeAttribute(d===Y?J[p.v.u8(p.u++)]:D[d])}}};break;case 17:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){e=p.v.u32(p.u,!0);p.u+=O;t.style.setProperty(p.s.substring(p.o,p.o+=e&U),p.s.substring(p.o,p.o+=(e&V)>>>W),a&S?a3:a4)}else{e=p.v.u32(p.u,!0);p.u+=R;t.style.setProperty(E[e&X],p.s.substring(p.o,p.o+=(e&a2)>>>T),a&S?a3:a4)};break;case 18:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){t.style.removeProperty(p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else{t.style.removeProperty(E[p.v.u8(p.u++)])};break;case 19:if(a&M){p.l=p.n[p.v.u32(p.u,!0)].cloneNode(!(a&S));p.u+=O}else{p.l=p.l.cloneNode(!(a&S))};if(a&N){p.n[p.v.u32(p.u,!0)]=p.l;p.u+=O};break;case 21:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};n=p.v.u8(p.u++);for(e=L;e<n;e++){f=p.v.u8(p.u++);if(f===X){u=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q}else{u=E[f]};t.style.setProperty(u,p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q};break;case 22:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};t.style.cssText=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q;break;case 23:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};d=p.decodeAttribute();if(a&N){g=c===a5?!t.hasAttribute(d):!t.hasAttributeNS(c,d)}else{g=a&S};if(g){if(c===a5){t.setAttribute(d,a4)}else{t.setAttributeNS(c,d,a4)}}else if(c===a5){t.removeAttribute(d)}else{t.removeAttributeNS(c,d)};break;case a6:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};n=p.v.u8(p.u++);for(e=L;e<n;e++){u=p.decodeAttribute();g=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q;if(c===a5){t.setAttribute(u,g)}else{t.setAttributeNS(c,u,g)}};break;case 25:u=p.decodeAttribute();s=p.v.u16(p.u,!0);p.u+=Q;if(a&M){g=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q};for(e=L;e<s;e++){t=p.n[p.v.u32(p.u,!0)];p.u+=O;if(!(a&M)){g=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q};if(c===a5){t.setAttribute(u,g)}else{t.setAttributeNS(c,u,g)}};break;case 26:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};u=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q;if(a&N){delete t.dataset[u]}else{t.dataset[u]=p.s.substring(p.o,p.o+=p.v.u16(p.u,!0));p.u+=Q};break;case 27:if(a&M){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};if(a&N){e=p.v.u32(p.u,!0);p.u+=O;t.insertData(e,p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q}else if(a&S){t.deleteData(p.v.u32(p.u,!0),p.v.u32(p.u+ O,!0));p.u+=T}else{t.appendData(p.s.substring(p.o,p.o+=p.v.u16(p.u,!0)));p.u+=Q};break;case 28:if(a&M){p.k.push(p.n[p.v.u32(p.u,!0)]);p.u+=O}else{p.k.push(p.l)};break;case 29:if(a&M){if(a&N){t=p.n[p.v.u32(p.u,!0)];p.u+=O}else{t=p.l};p.storePaths(t)}else{p.l=p.walkPath(p.l)};break;case 30:if(a&M){p.l=p.c.pop()}else{p.c.push(p.l)};break;case K:if(a&M){p.l=p.n[p.v.u32(p.u,!0)].cloneNode(!0);p.u+=O}else{p.l=p.l.cloneNode(!0)};p.storePaths(p.l);break;default:break}});let a,b,c,d,e,f,g,h,i,j,k,l,m,n,o,p,q,r,s,t,u,v,w;function x(){p.Work()}function y(a){p.UpdateMemory(a)}class A{constructor(a,b,c,d,e){this.l;this.n=[];this.p=[];this.k=[];this.c=[];this.UpdateMemory(a);this.lp;this.ls;this.m=b;this.pt=c;this.sp=d;this.sl=e;this.s=a4;this.o=L;this.d=new TextDecoder();this.i=P;p=this}NeedsMemory(){return this.v.buffer.byteLength===L}UpdateMemory(a){this.v=new a7(a.buffer);this.v.u32=this.v.getUint32;this.v.u16=this.v.getUint16;this.v.u8=this.v.getUint8}Work(){q=this.v.u8(this.m);if(q&P){this.lp=this.v.u32(this.pt,!0)};this.u=this.lp;if(q&O){b=this.v.u32(this.sl,!0);if(q&Q){this.ls=this.v.u32(this.sp,!0)};if(q&W){j=this.ls;k=j+ b;this.s=a4;if(j&P){while(j<k){this.s+=a8(this.v.u16(j,!0));j+=Q}}else{l=new Uint16Array(this.v.buffer,j,b>>>P);for(e=L;e<l.length;e+=a9){this.s+=a8.apply(a5,l.subarray(e,e+ a9))}}}else if(q&T){j=this.ls;this.s=a4;o=j+ (b/O|L)*O;while(j<o){m=this.v.u32(j);this.s+=a8(m>>a6,(m&aa)>>W,(m&Z)>>T,m&X);j+=O};switch(this.ls+ b- j){case R:m=this.v.u32(j);this.s+=a8(m>>a6,(m&aa)>>W,(m&Z)>>T);break;case Q:m=this.v.u16(j);this.s+=a8(m>>T,m&X);break;case P:this.s+=a8(this.v.u8(j));break;case L:break}}else{this.s=this.d.decode(new a7(this.v.buffer,this.ls,b))};this.o=L};for(;;){a=this.v.u32(this.u,!0);this.u+=O;if(z())return;a>>>=T;if(z())return;a>>>=T;if(z())return;a>>>=T;if(z())return}}createElement(){f=this.v.u32(this.u,!0);h=f&X;switch(h){case X:this.u+=Q;h=C[(f&Z)>>>T];return document.createElementNS(this.decodeNs(),h);case ab:this.u+=R;h=document.createElement(this.s.substring(this.o,this.o+=(f&a2)>>>T));return h;case Y:this.u+=Q;h=(f&Z)>>>T;if(h<F.length){return document.createElementNS(B[L],F[h])};return document.createElementNS(B[P],G[h- F.length]);case ac:this.u+=R;h=this.s.substring(this.o,this.o+=(f&a2)>>>T);return document.createElementNS(this.decodeNs(),h);default:this.u++;return document.createElement(C[h])}}createFullElement(){let a;f=this.v.u8(this.u++);if(f&P){a=this.v.u32(this.u,!0);this.u+=O};if(f&Q){t=document.createTextNode(this.s.substring(this.o,this.o+=this.v.u16(this.u,!0)));this.u+=Q;if(a!==a5){this.n[a]=t};return t}else{const b=this.createElement();f=this.v.u16(this.u,!0);this.u+=Q;n=f&X;const h=(f&Z)>>>T;for(e=L;e<n;e++){f=this.v.u32(this.u,!0);d=f&X;switch(d){case X:this.u+=Q;d=D[(f&Z)>>>T];c=this.decodeNs();b.setAttributeNS(c,d,this.s.substring(this.o,this.o+=this.v.u16(this.u,!0)));this.u+=Q;break;case ab:this.u++;f=this.v.u32(this.u,!0);this.u+=O;d=this.s.substring(this.o,this.o+=f&U);b.setAttribute(d,this.s.substring(this.o,this.o+=(f&V)>>>W));break;case Y:this.u+=O;b.setAttribute(J[(f&Z)>>>T],this.s.substring(this.o,this.o+=f>>>W));break;case _:;case $:this.u+=R;d=(d===_?a0:a1)+ this.s.substring(this.o,this.o+=(f&a2)>>>T);b.setAttribute(d,this.s.substring(this.o,this.o+=this.v.u16(this.u,!0)));this.u+=Q;break;case ac:this.u+=R;d=this.s.substring(this.o,this.o+=(f&a2)>>>T);c=this.decodeNs();g=this.s.substring(this.o,this.o+=this.v.u16(this.u,!0));this.u+=Q;b.setAttributeNS(c,d,g);break;default:this.u+=R;b.setAttribute(D[d],this.s.substring(this.o,this.o+=(f&a2)>>>T));break}};for(let a=L;a<h;a++){b.appendChild(this.createFullElement())};if(a!==a5){this.n[a]=b};return b}}decodeNs(){const a=this.v.u8(this.u++);if(a===X){b=this.v.u16(this.u,!0);this.u+=Q;return this.s.substring(this.o,this.o+=b)};return B[a]}decodeAttribute(){c=a5;f=this.v.u8(this.u++);switch(f){case X:d=D[this.v.u8(this.u++)];c=this.decodeNs();return d;case ab:this.u+=Q;return this.s.substring(this.o,this.o+=this.v.u16(this.u- Q,!0));case ac:this.u+=Q;d=this.s.substring(this.o,this.o+=this.v.u16(this.u- Q,!0));c=this.decodeNs();return d;case Y:return J[this.v.u8(this.u++)];case _:;case $:this.u+=Q;return (f===_?a0:a1)+ this.s.substring(this.o,this.o+=this.v.u16(this.u- Q,!0));default:return D[f]}}walkPath(a){k=this.u+ P+ this.v.u8(this.u);for(this.u++;this.u<k;this.u++){a=a.childNodes[this.v.u8(this.u)]};return a}storePaths(a){s=this.v.u16(this.u,!0);this.u+=Q;for(e=L;e<s;e++){t=this.walkPath(a);this.n[this.v.u32(this.u,!0)]=t;this.u+=O}}decodeStaticStr(){i=this.v.u32(this.u,!0);b=this.v.u32(this.u+ O,!0);this.u+=T;return this.d.decode(new a7(this.v.buffer,i,b))}decodeU32(){this.u+=O;return this.v.u32(this.u- O,!0)}AddNames(a,b,c){if(a){C.push(...a.split(ad))};if(b){D.push(...b.split(ad))};if(c){B.push(...c.split(ad))}}SetNode(a,b){this.n[a]=b}GetNode(a){return this.n[a]}}const B=[`http://www.w3.org/2000/svg`,`http://www.w3.org/1998/Math/MathML`,`http://www.w3.org/1999/xlink`,`http://www.w3.org/XML/1998/namespace`,`http://www.w3.org/2000/xmlns/`];const C=[ae,af,`acronym`,`address`,`applet`,`area`,`article`,`aside`,`audio`,`b`,`base`,`bdi`,`bdo`,`bgsound`,`big`,`blink`,`blockquote`,`body`,`br`,`button`,`canvas`,`caption`,`center`,ag,ah,`col`,`colgroup`,ai,aj,`datalist`,`dd`,`del`,`details`,`dfn`,`dialog`,ak,`div`,`dl`,`dt`,`em`,`embed`,`fieldset`,`figcaption`,`figure`,al,`footer`,am,an,`frameset`,`h1`,`head`,`header`,`hgroup`,`hr`,`html`,`i`,`iframe`,ao,`img`,`input`,`ins`,`kbd`,`keygen`,ap,`legend`,`li`,`link`,`main`,`map`,`mark`,`marquee`,`menu`,`menuitem`,`meta`,`meter`,`nav`,`nobr`,`noembed`,`noframes`,`noscript`,`object`,`ol`,`optgroup`,`option`,`output`,`p`,`param`,`picture`,`plaintext`,`portal`,`pre`,`progress`,`q`,`rb`,`rp`,`rt`,`rtc`,`ruby`,`s`,`samp`,aq,`section`,`select`,`shadow`,ar,`small`,`source`,`spacer`,at,`strike`,`strong`,au,`sub`,av,`sup`,`table`,`tbody`,`td`,`template`,`textarea`,`tfoot`,`th`,`thead`,`time`,aw,`tr`,`track`,`tt`,`u`,`ul`,`var`,`video`,`wbr`,`xmp`,`h2`,`h3`,`h4`,`h5`,`h6`,`search`];const D=[`accept-charset`,`accept`,`accesskey`,`action`,`align`,`allow`,`alt`,`aria-atomic`,`aria-busy`,`aria-controls`,`aria-current`,`aria-describedby`,`aria-description`,`aria-details`,`aria-disabled`,`aria-dropeffect`,`aria-errormessage`,`aria-flowto`,`aria-grabbed`,`aria-haspopup`,`aria-hidden`,`aria-invalid`,`aria-keyshortcuts`,`aria-label`,`aria-labelledby`,`aria-live`,`aria-owns`,`aria-relevant`,`aria-roledescription`,`async`,`autocapitalize`,`autocomplete`,`autofocus`,`autoplay`,ax,`bgcolor`,ay,`buffered`,`capture`,`challenge`,`charset`,`checked`,ag,`class`,ah,`codebase`,az,`cols`,`colspan`,ai,`contenteditable`,`contextmenu`,`controls`,`coords`,`crossorigin`,`csp`,aj,`datetime`,`decoding`,`default`,`defer`,ak,`dirname`,`disabled`,`download`,`draggable`,`enctype`,`enterkeyhint`,`for`,am,`formaction`,`formenctype`,`formmethod`,`formnovalidate`,`formtarget`,`headers`,aA,`hidden`,`high`,`href`,`hreflang`,`http-equiv`,`icon`,`id`,`importance`,`inputmode`,`integrity`,`intrinsicsize`,`ismap`,`itemprop`,`keytype`,`kind`,ap,`lang`,`language`,`list`,`loading`,`loop`,`low`,`manifest`,`max`,`maxlength`,`media`,`method`,`min`,`minlength`,`multiple`,`muted`,`name`,`novalidate`,`open`,`optimum`,aB,`ping`,`placeholder`,`poster`,`preload`,`radiogroup`,`readonly`,`referrerpolicy`,`rel`,`required`,`reversed`,`role`,`rows`,`rowspan`,`sandbox`,`scope`,`scoped`,`selected`,`shape`,`size`,`sizes`,ar,at,`spellcheck`,`src`,`srcdoc`,`srclang`,`srcset`,`start`,`step`,au,av,`tabindex`,`target`,aw,aC,`type`,`usemap`,`value`,aD,`wrap`,`aria-activedescendant`,`aria-autocomplete`,`aria-checked`,`aria-colcount`,`aria-colindex`,`aria-colspan`,`aria-expanded`,`aria-level`,`aria-modal`,`aria-multiline`,`aria-multiselectable`,`aria-orientation`,`aria-placeholder`,`aria-posinset`,`aria-pressed`,`aria-readonly`,`aria-required`,`aria-rowcount`,`aria-rowindex`,`aria-rowspan`,`aria-selected`,`aria-setsize`,`aria-sort`,`aria-valuemax`,`aria-valuemin`,`aria-valuenow`,`aria-valuetext`,af,`as`,`blocking`,`exportparts`,`fetchpriority`,`imagesizes`,`imagesrcset`,`inert`,`is`,`itemid`,`itemref`,`itemscope`,`itemtype`,`nomodule`,`nonce`,`part`,`playsinline`,`popover`,`popovertarget`,`popovertargetaction`,`shadowrootmode`,`aria-braillelabel`,`aria-brailleroledescription`,`aria-colindextext`,`aria-rowindextext`];const E=[`align-content`,`align-items`,`align-self`,`animation`,`animation-delay`,`animation-duration`,`animation-name`,`animation-play-state`,`aspect-ratio`,`backdrop-filter`,ax,`background-color`,`background-image`,`background-position`,`background-repeat`,`background-size`,ay,`border-bottom`,`border-color`,`border-left`,`border-radius`,`border-right`,`border-style`,`border-top`,`border-width`,`bottom`,`box-shadow`,`box-sizing`,`caret-color`,aE,az,`column-gap`,ai,aF,aG,aH,aI,`flex`,`flex-basis`,`flex-direction`,`flex-grow`,`flex-shrink`,`flex-wrap`,al,aJ,aK,aL,aM,`gap`,`grid-area`,`grid-column`,`grid-row`,`grid-template-areas`,`grid-template-columns`,`grid-template-rows`,aA,`inset`,`justify-content`,`justify-items`,`justify-self`,`left`,aN,`line-height`,`list-style`,`margin`,`margin-bottom`,`margin-left`,`margin-right`,`margin-top`,`max-height`,`max-width`,`min-height`,`min-width`,`mix-blend-mode`,`object-fit`,`object-position`,aO,aP,`outline`,`outline-offset`,aQ,`overflow-wrap`,`overflow-x`,`overflow-y`,`padding`,`padding-bottom`,`padding-left`,`padding-right`,`padding-top`,aR,`position`,`resize`,`right`,`row-gap`,`scroll-behavior`,aS,aT,`text-align`,aU,`text-overflow`,`text-shadow`,`text-transform`,`top`,aV,aW,`transition`,`transition-delay`,`transition-duration`,`transition-property`,`transition-timing-function`,aC,aX,aY,`user-select`,`vertical-align`,aZ,`white-space`,aD,`will-change`,`word-break`,`z-index`];const F=[`svg`,ae,`animate`,`animateMotion`,`animateTransform`,`circle`,`clipPath`,`defs`,`desc`,`ellipse`,`feBlend`,`feColorMatrix`,`feComponentTransfer`,`feComposite`,`feConvolveMatrix`,`feDiffuseLighting`,`feDisplacementMap`,`feDistantLight`,`feDropShadow`,`feFlood`,`feFuncA`,`feFuncB`,`feFuncG`,`feFuncR`,`feGaussianBlur`,`feImage`,`feMerge`,`feMergeNode`,`feMorphology`,`feOffset`,`fePointLight`,`feSpecularLighting`,`feSpotLight`,`feTile`,`feTurbulence`,aI,`foreignObject`,`g`,ao,`line`,`linearGradient`,`marker`,a_,`metadata`,`mpath`,a$,aB,`polygon`,`polyline`,`radialGradient`,`rect`,aq,`set`,`stop`,au,`switch`,`symbol`,`text`,`textPath`,aw,`tspan`,`use`,`view`];const G=[`math`,`annotation`,`annotation-xml`,`maction`,`menclose`,`merror`,`mfrac`,`mi`,`mmultiscripts`,`mn`,`mo`,`mover`,`mpadded`,`mphantom`,`mprescripts`,`mroot`,`mrow`,`ms`,`mspace`,`msqrt`,`mstyle`,`msub`,`msubsup`,`msup`,`mtable`,`mtd`,`mtext`,`mtr`,`munder`,`munderover`,`semantics`];const H=[`accumulate`,`additive`,`alignment-baseline`,`attributeName`,`attributeType`,`baseFrequency`,`baseline-shift`,`begin`,`bias`,`by`,`calcMode`,`clip`,aE,`clip-rule`,`clipPathUnits`,`color-interpolation`,`color-interpolation-filters`,aF,`cx`,`cy`,`d`,`diffuseConstant`,`direction`,aG,`divisor`,`dominant-baseline`,`dur`,`dx`,`dy`,`edgeMode`,`elevation`,`end`,`exponent`,aH,`fill-opacity`,`fill-rule`,aI,`filterUnits`,`flood-color`,`flood-opacity`,aJ,aK,`font-size-adjust`,`font-stretch`,aL,`font-variant`,aM,`fr`,`from`,`fx`,`fy`,`gradientTransform`,`gradientUnits`,`image-rendering`,`in`,`in2`,`intercept`,`k1`,`k2`,`k3`,`k4`,`kernelMatrix`,`kernelUnitLength`,`keyPoints`,`keySplines`,`keyTimes`,`lengthAdjust`,aN,`lighting-color`,`limitingConeAngle`,`marker-end`,`marker-mid`,`marker-start`,`markerHeight`,`markerUnits`,`markerWidth`,a_,`maskContentUnits`,`maskUnits`,`mode`,`numOctaves`,`offset`,aO,`operator`,aP,`orient`,`origin`,aQ,`paint-order`,a$,`pathLength`,`patternContentUnits`,`patternTransform`,`patternUnits`,aR,`points`,`pointsAtX`,`pointsAtY`,`pointsAtZ`,`preserveAlpha`,`preserveAspectRatio`,`primitiveUnits`,`r`,`radius`,`refX`,`refY`,`repeatCount`,`repeatDur`,`restart`,`result`,aX,`rx`,`ry`,aY,`seed`,`shape-rendering`,`specularConstant`,`specularExponent`,`spreadMethod`,`startOffset`,`stdDeviation`,`stitchTiles`,`stop-color`,`stop-opacity`,aS,`stroke-dasharray`,`stroke-dashoffset`,`stroke-linecap`,`stroke-linejoin`,`stroke-miterlimit`,`stroke-opacity`,aT,`surfaceScale`,`systemLanguage`,`tableValues`,`targetX`,`targetY`,`text-anchor`,aU,`text-rendering`,`textLength`,`to`,aV,aW,`unicode-bidi`,`values`,`vector-effect`,`viewBox`,aZ,`word-spacing`,`writing-mode`,`x`,`x1`,`x2`,`xChannelSelector`,`y`,`y1`,`y2`,`yChannelSelector`,`z`];const I=[`accent`,`accentunder`,`columnalign`,`columnlines`,`columnspacing`,`columnspan`,`depth`,aG,`displaystyle`,`encoding`,`fence`,an,`framespacing`,`largeop`,`linethickness`,`lspace`,`mathbackground`,`mathcolor`,`mathsize`,`mathvariant`,`maxsize`,`minsize`,`movablelimits`,`notation`,`rowalign`,`rowlines`,`rowspacing`,`rspace`,`scriptlevel`,`separator`,`stretchy`,`symmetric`,`voffset`];const J=[...H,...I];export{x as work_last_created,y as update_last_memory,A as JsInterpreter}
\ No newline at end of file
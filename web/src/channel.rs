@@ -4,13 +4,15 @@
 //!
 
 use sledgehammer_encoder::{
-    batch::{Batch, PreparedBatch},
-    MaybeId, NodeId, Op, TextBuilder, WritableText,
+    attribute::AnyAttribute,
+    batch::{Batch, PreparedBatch, StringEncoding},
+    style::AnyStyle,
+    CustomNames, MaybeId, NodeId, Op, OwnedElementBuilder, TextBuilder, WritableText,
 };
 use web_sys::Node;
 
 use crate::{
-    update_last_memory, work_last_created, ElementBuilder, IntoAttribue, IntoElement,
+    update_last_memory, work_last_created, ElementBuilder, IntoAttribue, IntoElement, IntoStyle,
     JsInterpreter, MSG_METADATA_PTR, MSG_PTR_PTR, STR_LEN_PTR, STR_PTR_PTR,
 };
 
@@ -28,6 +30,29 @@ pub struct MsgChannel {
 
 impl Default for MsgChannel {
     fn default() -> Self {
+        Self::with_custom_names(&CustomNames::default())
+    }
+}
+
+impl MsgChannel {
+    /// Create a new [`MsgChannel`] that can encode the custom element names, attribute names and namespaces in `names` with a single byte.
+    ///
+    /// Batches built with the `html!` macro must list the custom names in the same order in their `sledgehammer-elements` and `sledgehammer-attributes` attributes. This is not checked.
+    ///
+    /// Example:
+    /// ```no_run
+    /// use sledgehammer::{CustomNames, MaybeId, MsgChannel};
+    ///
+    /// let mut names = CustomNames::new();
+    /// let icon = names.element("x-icon");
+    /// let row_id = names.attribute("data-row-id");
+    ///
+    /// let mut channel = MsgChannel::with_custom_names(&names);
+    /// channel.create_element(icon, None);
+    /// channel.set_attribute(row_id, 7, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn with_custom_names(names: &CustomNames) -> Self {
         unsafe {
             debug_assert!(
                 !INTERPRETER_EXISTS,
@@ -52,15 +77,24 @@ impl Default for MsgChannel {
             )
         };
 
+        if !names.elements().is_empty()
+            || !names.attributes().is_empty()
+            || !names.namespaces().is_empty()
+        {
+            js_interpreter.AddNames(
+                &names.elements().join(" "),
+                &names.attributes().join(" "),
+                &names.namespaces().join(" "),
+            );
+        }
+
         Self {
             js_interpreter,
             last_mem_size: 0,
             batch: Batch::default(),
         }
     }
-}
 
-impl MsgChannel {
     /// IMPORTANT: This method is exicuted immediatly and does not wait for the next flush
     ///
     /// Example:
@@ -93,6 +127,27 @@ impl MsgChannel {
         self.js_interpreter.GetNode(id.0)
     }
 
+    /// Change how the strings of queued operations are sent to the interpreter. Utf-16 strings are read without a `TextDecoder`, which is faster for batches with many short strings at the cost of a larger string buffer.
+    ///
+    /// The queued operations are flushed before the encoding changes.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.set_string_encoding(StringEncoding::Utf16);
+    /// channel.create_element("div", None);
+    /// channel.set_text("hello world", MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_string_encoding(&mut self, encoding: StringEncoding) {
+        if self.batch.string_encoding() != encoding {
+            if !self.batch.str_buf.is_empty() {
+                self.flush();
+            }
+            self.batch.set_string_encoding(encoding);
+        }
+    }
+
     /// Exicutes any queued operations in the order they were added
     ///
     /// Example:
@@ -110,12 +165,10 @@ impl MsgChannel {
         run_batch(
             &self.batch.msg,
             &self.batch.str_buf,
+            self.batch.string_encoding(),
             &mut self.last_mem_size,
         );
-        self.batch.msg.clear();
-        self.batch.current_op_batch_idx = 0;
-        self.batch.current_op_byte_idx = 3;
-        self.batch.str_buf.clear();
+        self.batch.clear();
     }
 
     /// Appends a number of nodes as children of the given node.
@@ -134,6 +187,96 @@ impl MsgChannel {
         self.batch.append_child(root, child)
     }
 
+    /// Push a node onto the node stack. The nodes on the stack are consumed by [`MsgChannel::append_children`], [`MsgChannel::replace_with_stack`], [`MsgChannel::insert_stack_after`] and [`MsgChannel::insert_stack_before`].
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("ul", Some(NodeId(0)));
+    /// channel.create_element("li", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_element("li", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// // append both <li> elements to the <ul> element
+    /// channel.append_children(MaybeId::Node(NodeId(0)), 2);
+    /// channel.flush();
+    /// ```
+    pub fn push_root(&mut self, id: MaybeId) {
+        self.batch.push_root(id)
+    }
+
+    /// Pop the topmost `n` nodes from the node stack and append them to a node in the order they were pushed.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_text_node("Hello", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_text_node("world", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_element("p", None);
+    /// // the <p> element now contains "Helloworld"
+    /// channel.append_children(MaybeId::LastNode, 2);
+    /// channel.flush();
+    /// ```
+    pub fn append_children(&mut self, root: MaybeId, n: u16) {
+        self.batch.append_children(root, n)
+    }
+
+    /// Pop the topmost `n` nodes from the node stack and replace a node with them in the order they were pushed.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", Some(NodeId(0)));
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// // replace the <div> element with the two <p> elements
+    /// channel.replace_with_stack(MaybeId::Node(NodeId(0)), 2);
+    /// channel.flush();
+    /// ```
+    pub fn replace_with_stack(&mut self, root: MaybeId, n: u16) {
+        self.batch.replace_with_stack(root, n)
+    }
+
+    /// Pop the topmost `n` nodes from the node stack and insert them after a node in the order they were pushed.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", Some(NodeId(0)));
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// // insert the two <p> elements after the <div> element
+    /// channel.insert_stack_after(MaybeId::Node(NodeId(0)), 2);
+    /// channel.flush();
+    /// ```
+    pub fn insert_stack_after(&mut self, root: MaybeId, n: u16) {
+        self.batch.insert_stack_after(root, n)
+    }
+
+    /// Pop the topmost `n` nodes from the node stack and insert them before a node in the order they were pushed.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", Some(NodeId(0)));
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// channel.create_element("p", None);
+    /// channel.push_root(MaybeId::LastNode);
+    /// // insert the two <p> elements before the <div> element
+    /// channel.insert_stack_before(MaybeId::Node(NodeId(0)), 2);
+    /// channel.flush();
+    /// ```
+    pub fn insert_stack_before(&mut self, root: MaybeId, n: u16) {
+        self.batch.insert_stack_before(root, n)
+    }
+
     /// Replace a node with another node
     ///
     /// Example:
@@ -250,6 +393,20 @@ impl MsgChannel {
         self.batch.create_text_node(text, id)
     }
 
+    /// Create a new text node from a static string. The interpreter reads the text directly from wasm memory instead of copying it into the message.
+    ///
+    /// Example:
+    /// ```no_run
+    /// static TERMS: &str = "A long block of text that is already in the data segment...";
+    /// let mut channel = MsgChannel::default();
+    /// // create a text node without copying the text
+    /// channel.create_static_text_node(TERMS, None);
+    /// channel.flush();
+    /// ```
+    pub fn create_static_text_node(&mut self, text: &'static str, id: Option<NodeId>) {
+        self.batch.create_static_text_node(text, id)
+    }
+
     /// Create a new element node
     ///
     /// Example:
@@ -278,6 +435,65 @@ impl MsgChannel {
         self.batch.set_text(text, root)
     }
 
+    /// Append text to the end of a text node without resending the text that is already in the node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_text_node("Hello", Some(NodeId(0)));
+    /// channel.flush();
+    /// // only ", world" is sent
+    /// channel.append_text(", world", MaybeId::Node(NodeId(0)));
+    /// channel.flush();
+    /// ```
+    pub fn append_text(&mut self, text: impl WritableText, id: MaybeId) {
+        self.batch.append_text(text, id)
+    }
+
+    /// Insert text into a text node. The offset is in utf-16 code units.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_text_node("Hello world", None);
+    /// // the text node now contains "Hello, world"
+    /// channel.insert_text(5, ",", MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn insert_text(&mut self, offset: u32, text: impl WritableText, id: MaybeId) {
+        self.batch.insert_text(offset, text, id)
+    }
+
+    /// Delete text from a text node. The offset and count are in utf-16 code units.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_text_node("Hello, world", None);
+    /// // the text node now contains "Hello"
+    /// channel.delete_text(5, 7, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn delete_text(&mut self, offset: u32, count: u32, id: MaybeId) {
+        self.batch.delete_text(offset, count, id)
+    }
+
+    /// Set the text content of a node to a static string. The interpreter reads the text directly from wasm memory instead of copying it into the message.
+    ///
+    /// Example:
+    /// ```no_run
+    /// static TERMS: &str = "A long block of text that is already in the data segment...";
+    /// let mut channel = MsgChannel::default();
+    /// // create a <pre> element
+    /// channel.create_element("pre", None);
+    /// // set the text content of the <pre> element without copying the text
+    /// channel.set_static_text(TERMS, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_static_text(&mut self, text: &'static str, root: MaybeId) {
+        self.batch.set_static_text(text, root)
+    }
+
     /// Set the value of a node's attribute.
     ///
     /// Example:
@@ -287,6 +503,8 @@ impl MsgChannel {
     /// channel.create_element("div", None);
     /// // set the attribute "id" to "my-div" on the <div> element
     /// channel.set_attribute(Attribute::id, "my-div", MaybeId::LastNode);
+    /// // set the attribute "data-row-id" to 7 on the <div> element, only "row-id" is sent as a string
+    /// channel.set_attribute(data("row-id"), 7, MaybeId::LastNode);
     /// channel.flush();
     /// ```
     pub fn set_attribute<'a, 'b>(
@@ -314,6 +532,137 @@ impl MsgChannel {
         self.batch.remove_attribute(attr, root)
     }
 
+    /// Set many attributes on a node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <input> element
+    /// channel.create_element("input", None);
+    /// // set the attributes "type", "name" and "placeholder" on the <input> element
+    /// channel.set_attributes(
+    ///     &[
+    ///         (Attribute::r#type.into(), "text"),
+    ///         (Attribute::name.into(), "user"),
+    ///         ("placeholder".into(), "Name"),
+    ///     ],
+    ///     MaybeId::LastNode,
+    /// );
+    /// channel.flush();
+    /// ```
+    pub fn set_attributes(&mut self, attributes: &[(AnyAttribute, &str)], root: MaybeId) {
+        self.batch.set_attributes(attributes, root)
+    }
+
+    /// Set an attribute on many nodes with a different value for each node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create two <div> elements
+    /// channel.create_element("div", Some(NodeId(1)));
+    /// channel.create_element("div", Some(NodeId(2)));
+    /// // set the attribute "id" on both <div> elements
+    /// channel.set_attribute_many(Attribute::id, &[(NodeId(1), "first"), (NodeId(2), "second")]);
+    /// channel.flush();
+    /// ```
+    pub fn set_attribute_many<'a, 'b>(
+        &mut self,
+        attr: impl IntoAttribue<'a, 'b>,
+        values: &[(NodeId, &str)],
+    ) {
+        self.batch.set_attribute_many(attr, values)
+    }
+
+    /// Set an attribute on many nodes with the same value for every node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create two <div> elements
+    /// channel.create_element("div", Some(NodeId(1)));
+    /// channel.create_element("div", Some(NodeId(2)));
+    /// // set the attribute "class" to "selected" on both <div> elements
+    /// channel.set_attribute_all(Attribute::class, "selected", &[NodeId(1), NodeId(2)]);
+    /// channel.flush();
+    /// ```
+    pub fn set_attribute_all<'a, 'b>(
+        &mut self,
+        attr: impl IntoAttribue<'a, 'b>,
+        value: impl WritableText,
+        ids: &[NodeId],
+    ) {
+        self.batch.set_attribute_all(attr, value, ids)
+    }
+
+    /// Set a property of a node's dataset. The key is the camel case name of the property, so `rowId` sets the `data-row-id` attribute.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <tr> element
+    /// channel.create_element("tr", None);
+    /// // set the attribute "data-row-id" to 7 on the <tr> element
+    /// channel.set_dataset("rowId", 7, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_dataset(&mut self, key: &str, value: impl WritableText, root: MaybeId) {
+        self.batch.set_dataset(key, value, root)
+    }
+
+    /// Remove a property from a node's dataset.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <tr> element
+    /// channel.create_element("tr", None);
+    /// channel.set_dataset("rowId", 7, MaybeId::LastNode);
+    /// // remove the attribute "data-row-id" from the <tr> element
+    /// channel.remove_dataset("rowId", MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn remove_dataset(&mut self, key: &str, root: MaybeId) {
+        self.batch.remove_dataset(key, root)
+    }
+
+    /// Add a boolean attribute to a node if the value is true or remove it if the value is false.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <button> element
+    /// channel.create_element("button", None);
+    /// // add the attribute "disabled" to the <button> element
+    /// channel.set_bool_attribute(Attribute::disabled, true, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_bool_attribute<'a, 'b>(
+        &mut self,
+        attr: impl IntoAttribue<'a, 'b>,
+        value: bool,
+        root: MaybeId,
+    ) {
+        self.batch.set_bool_attribute(attr, value, root)
+    }
+
+    /// Remove a boolean attribute from a node if the node has it or add it if the node does not have it.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <div> element
+    /// channel.create_element("div", None);
+    /// // hide the <div> element
+    /// channel.toggle_attribute(Attribute::hidden, MaybeId::LastNode);
+    /// // show the <div> element again
+    /// channel.toggle_attribute(Attribute::hidden, MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn toggle_attribute<'a, 'b>(&mut self, attr: impl IntoAttribue<'a, 'b>, root: MaybeId) {
+        self.batch.toggle_attribute(attr, root)
+    }
+
     /// Clone a node and store it with a new id.
     ///
     /// Example:
@@ -329,6 +678,51 @@ impl MsgChannel {
         self.batch.clone_node(id, new_id)
     }
 
+    /// Clone a node without its children and store it with a new id.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a <ul> element with a <li> child
+    /// channel.build_full_element(
+    ///     ElementBuilder::new(Element::ul.into())
+    ///         .children(&[ElementBuilder::new(Element::li.into()).into()]),
+    /// );
+    /// // clone the <ul> element without the <li> and store it with the id 1
+    /// channel.clone_node_shallow(MaybeId::LastNode, MaybeId::Node(NodeId(1)));
+    /// channel.flush();
+    /// ```
+    pub fn clone_node_shallow(&mut self, id: MaybeId, new_id: MaybeId) {
+        self.batch.clone_node_shallow(id, new_id)
+    }
+
+    /// Clone a node and store the nodes at paths of child indices in the clone with ids. An empty path stores the clone itself. The clone becomes the last node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a template: <div><h1></h1><p></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .id(NodeId(0))
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::h1.into())
+    ///                 .into(),
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// // clone the template, store the clone with the id 1, the <h1> with the id 2 and the <p> with the id 3
+    /// channel.clone_node_with_ids(
+    ///     MaybeId::Node(NodeId(0)),
+    ///     &[(&[], NodeId(1)), (&[0], NodeId(2)), (&[1], NodeId(3))],
+    /// );
+    /// channel.flush();
+    /// ```
+    pub fn clone_node_with_ids(&mut self, id: MaybeId, paths: &[(&[u8], NodeId)]) {
+        self.batch.clone_node_with_ids(id, paths)
+    }
+
     /// Move the last node to the first child
     ///
     /// Example:
@@ -401,6 +795,137 @@ impl MsgChannel {
         self.batch.parent_node()
     }
 
+    /// Save the last node on the cursor stack so it can be restored with [`MsgChannel::pop_cursor`] after navigating away from it.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a element: <div><p></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// channel.push_cursor();
+    /// // move from the <div> to the <p>
+    /// channel.first_child();
+    /// channel.set_text("Hello world", MaybeId::LastNode);
+    /// // move back to the <div>
+    /// channel.pop_cursor();
+    /// channel.flush();
+    /// ```
+    pub fn push_cursor(&mut self) {
+        self.batch.push_cursor()
+    }
+
+    /// Restore the last node that was saved with [`MsgChannel::push_cursor`]. See [`MsgChannel::push_cursor`] for an example.
+    pub fn pop_cursor(&mut self) {
+        self.batch.pop_cursor()
+    }
+
+    /// Move the last node to the last child of the last node.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a element: <div><h1></h1><p></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::h1.into())
+    ///                 .into(),
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// // move from the <div> to the <p>
+    /// channel.last_child();
+    /// // move from the <p> to the <h1>
+    /// channel.previous_sibling();
+    /// channel.flush();
+    /// ```
+    pub fn last_child(&mut self) {
+        self.batch.last_child()
+    }
+
+    /// Move the last node to the child of the last node at an index. The index counts every child node including text and comment nodes.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a element: <div><h1></h1><p></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::h1.into())
+    ///                 .into(),
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// // move from the <div> to the <p>
+    /// channel.nth_child(1);
+    /// channel.flush();
+    /// ```
+    pub fn nth_child(&mut self, n: u32) {
+        self.batch.nth_child(n)
+    }
+
+    /// Move the last node to the previous sibling of the last node. See [`MsgChannel::last_child`] for an example.
+    pub fn previous_sibling(&mut self) {
+        self.batch.previous_sibling()
+    }
+
+    /// Move the last node along a path of child indices in a single operation.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a element: <div><h1></h1><p><span></span></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::h1.into())
+    ///                 .into(),
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .children(&[ElementBuilder::new(Element::span.into()).into()])
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// // move from the <div> to the <span>
+    /// channel.navigate(&[1, 0]);
+    /// channel.flush();
+    /// ```
+    pub fn navigate(&mut self, path: &[u8]) {
+        self.batch.navigate(path)
+    }
+
+    /// Store the nodes at paths of child indices from a node with ids in a single operation. The last node is not changed.
+    ///
+    /// Example:
+    /// ```no_run
+    /// let mut channel = MsgChannel::default();
+    /// // create a element: <div><h1></h1><p><span></span></p></div>
+    /// channel.build_full_element(
+    ///     ElementBuilder::new("div".into())
+    ///         .children(&[
+    ///             ElementBuilder::new(Element::h1.into())
+    ///                 .into(),
+    ///             ElementBuilder::new(Element::p.into())
+    ///                 .children(&[ElementBuilder::new(Element::span.into()).into()])
+    ///                 .into(),
+    ///         ]),
+    /// );
+    /// // store the <h1> with NodeId(0) and the <span> with NodeId(1)
+    /// channel.store_paths(MaybeId::LastNode, &[(&[0], NodeId(0)), (&[1, 0], NodeId(1))]);
+    /// channel.flush();
+    /// ```
+    pub fn store_paths(&mut self, root: MaybeId, paths: &[(&[u8], NodeId)]) {
+        self.batch.store_paths(root, paths)
+    }
+
     /// Store the last node with the given id. This is useful when traversing the document tree.
     ///
     /// Example:
@@ -474,6 +999,22 @@ impl MsgChannel {
         self.batch.build_full_element(el)
     }
 
+    /// Build a full element from an [`OwnedElementBuilder`]. This is encoded the same way as [`MsgChannel::build_full_element`], but the attributes and children can be added at runtime.
+    ///
+    /// Example:
+    /// ```rust
+    /// let mut channel = MsgChannel::default();
+    /// let mut el = OwnedElementBuilder::new("div").id(NodeId(0));
+    /// el.push_attr(Attribute::style, "color: blue");
+    /// el.push_child(OwnedElementBuilder::new(Element::p));
+    /// el.text("Hello from sledgehammer!");
+    /// channel.build_owned_element(&el);
+    /// channel.flush();
+    /// ```
+    pub fn build_owned_element(&mut self, el: &OwnedElementBuilder) {
+        self.batch.build_owned_element(el)
+    }
+
     /// Build a text node
     ///
     /// Example:
@@ -497,12 +1038,71 @@ impl MsgChannel {
     /// channel.create_element("div", None);
     /// // set the style property "color" to "blue"
     /// channel.set_style("color", "blue", MaybeId::LastNode);
+    /// // common properties can be encoded with a single byte
+    /// channel.set_style(Style::opacity, "0.5", MaybeId::LastNode);
     /// channel.flush();
     /// ```
-    pub fn set_style(&mut self, style: &str, value: &str, id: MaybeId) {
+    pub fn set_style<'a>(
+        &mut self,
+        style: impl IntoStyle<'a>,
+        value: impl WritableText,
+        id: MaybeId,
+    ) {
         self.batch.set_style(style, value, id)
     }
 
+    /// Set a style property on a node with the `important` priority.
+    ///
+    /// Example:
+    /// ```rust
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", None);
+    /// // set the style property "color" to "blue" even if a stylesheet marks it as important
+    /// channel.set_style_important(Style::color, "blue", MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_style_important<'a>(
+        &mut self,
+        style: impl IntoStyle<'a>,
+        value: impl WritableText,
+        id: MaybeId,
+    ) {
+        self.batch.set_style_important(style, value, id)
+    }
+
+    /// Set a number of style properties on a node in a single operation.
+    ///
+    /// Example:
+    /// ```rust
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", None);
+    /// channel.set_styles(
+    ///     &[
+    ///         (Style::transform.into(), "translate(10px, 20px)"),
+    ///         (Style::opacity.into(), "0.5"),
+    ///         ("--hue".into(), "120"),
+    ///     ],
+    ///     MaybeId::LastNode,
+    /// );
+    /// channel.flush();
+    /// ```
+    pub fn set_styles(&mut self, styles: &[(AnyStyle, &str)], id: MaybeId) {
+        self.batch.set_styles(styles, id)
+    }
+
+    /// Replace all inline styles of a node with the css text.
+    ///
+    /// Example:
+    /// ```rust
+    /// let mut channel = MsgChannel::default();
+    /// channel.create_element("div", None);
+    /// channel.set_css_text("color: blue; opacity: 0.5", MaybeId::LastNode);
+    /// channel.flush();
+    /// ```
+    pub fn set_css_text(&mut self, text: impl WritableText, id: MaybeId) {
+        self.batch.set_css_text(text, id)
+    }
+
     /// Remove a style property from a node.
     ///
     /// Example:
@@ -514,11 +1114,11 @@ impl MsgChannel {
     /// channel.remove_style("color", MaybeId::LastNode);
     /// channel.flush();
     /// ```
-    pub fn remove_style(&mut self, style: &str, id: MaybeId) {
+    pub fn remove_style<'a>(&mut self, style: impl IntoStyle<'a>, id: MaybeId) {
         self.batch.remove_style(style, id)
     }
 
-    /// Adds a batch of operations to the current batch.
+    /// Adds a batch of operations to the current batch. The strings in a batch with a different string encoding are converted to the string encoding of the channel.
     ///
     /// Example:
     /// ```rust
@@ -546,11 +1146,21 @@ impl MsgChannel {
     /// channel.run_batch(&batch.finalize());
     /// ```
     pub fn run_batch(&mut self, batch: impl PreparedBatch) {
-        run_batch(batch.msg(), batch.str(), &mut self.last_mem_size);
+        run_batch(
+            batch.msg(),
+            batch.str(),
+            batch.string_encoding(),
+            &mut self.last_mem_size,
+        );
     }
 }
 
-fn run_batch(msg: &[u8], str_buf: &[u8], last_mem_size: &mut usize) {
+fn run_batch(
+    msg: &[u8],
+    str_buf: &[u8],
+    string_encoding: StringEncoding,
+    last_mem_size: &mut usize,
+) {
     debug_assert_eq!(0usize.to_le_bytes().len(), 32 / 8);
     let msg_ptr = msg.as_ptr() as usize;
     let str_ptr = str_buf.as_ptr() as usize;
@@ -601,9 +1211,15 @@ fn run_batch(msg: &[u8], str_buf: &[u8], last_mem_size: &mut usize) {
             *mut_metadata_ptr |= 1 << 2;
             let mut_str_len_ptr: *mut usize = std::mem::transmute(STR_LEN_PTR);
             *mut_str_len_ptr = str_buf.len() as usize;
-            if *mut_str_len_ptr < 100 {
-                // the fourth bit encodes if the strings are entirely ascii and small
-                *mut_metadata_ptr |= (str_buf.is_ascii() as u8) << 3;
+            match string_encoding {
+                StringEncoding::Utf8 => {
+                    if *mut_str_len_ptr < 100 {
+                        // the fourth bit encodes if the strings are entirely ascii and small
+                        *mut_metadata_ptr |= (str_buf.is_ascii() as u8) << 3;
+                    }
+                }
+                // the fifth bit encodes if the strings are utf-16 code units
+                StringEncoding::Utf16 => *mut_metadata_ptr |= 1 << 4,
             }
         }
     }
@@ -74,14 +74,19 @@
 #![allow(non_camel_case_types)]
 
 pub mod channel;
+mod tables;
 
 pub use channel::MsgChannel;
 pub use sledgehammer_encoder::{
-    Attribute, Element, ElementBuilder, IntoAttribue, IntoElement, MaybeId, NodeBuilder, NodeId,
-    StaticBatch, TextBuilder, WritableText,
+    aria, data, Attribute, AttributePrefix, CustomAttribute, CustomElement, CustomNames,
+    CustomNamespace, DisplayText, Element, ElementBuilder, IntoAttribue, IntoElement, IntoStyle,
+    KnownNamespace, MathMlAttribute, MathMlElement, MaybeId, Namespace, NodeBuilder, NodeId,
+    OwnedElementBuilder, OwnedNodeBuilder, OwnedTextBuilder, PrefixedAttribute, StaticBatch,
+    StringEncoding, Style, SvgAttribute, SvgElement, TextBuilder, WritableText,
 };
 
 pub use sledgehammer_encoder;
+pub use sledgehammer_encoder::dom;
 
 use wasm_bindgen::prelude::*;
 use web_sys::Node;
@@ -124,6 +129,14 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub(crate) fn UpdateMemory(this: &JsInterpreter, mem: JsValue);
 
+    #[wasm_bindgen(method)]
+    pub(crate) fn AddNames(
+        this: &JsInterpreter,
+        elements: &str,
+        attributes: &str,
+        namespaces: &str,
+    );
+
     #[wasm_bindgen(method)]
     pub(crate) fn SetNode(this: &JsInterpreter, id: u32, node: Node);
 
@@ -0,0 +1,238 @@
+//! The interpreter looks up built-in elements, attributes and namespaces by their discriminant, so the tables in the interpreter must list the same names in the same order as [`Element`], [`Attribute`], [`KnownNamespace`], [`Style`] and the extended SVG and MathML tables.
+//! This is checked for interpreter.js and both optimized copies of it when the crate is compiled.
+
+use crate::{
+    Attribute, Element, KnownNamespace, MathMlAttribute, MathMlElement, Style, SvgAttribute,
+    SvgElement,
+};
+
+const INTERPRETER: &[u8] = include_bytes!("../interpreter.js");
+const INTERPRETER_MANUALLY_OPT: &[u8] = include_bytes!("../interpreter_manually_opt.js");
+const INTERPRETER_OPT: &[u8] = include_bytes!("../interpreter_opt.js");
+
+/// The names of every variant of a name table ordered by discriminant
+macro_rules! names {
+    ($table:ident) => {{
+        let mut names = [""; $table::ALL.len()];
+        let mut i = 0;
+        while i < names.len() {
+            names[i] = $table::ALL[i].as_str();
+            i += 1;
+        }
+        names
+    }};
+}
+
+/// Checks a name table against the interpreter and both optimized copies of it
+macro_rules! check_table {
+    ($table:ident, $js:literal) => {
+        const _: () = assert!(
+            js_table_matches(
+                INTERPRETER,
+                concat!("const ", $js, " = [").as_bytes(),
+                &names!($table)
+            ),
+            concat!(
+                "the ",
+                $js,
+                " table in interpreter.js does not match ",
+                stringify!($table)
+            )
+        );
+
+        const _: () = assert!(
+            js_table_matches(
+                INTERPRETER_MANUALLY_OPT,
+                concat!("const ", $js, " = [").as_bytes(),
+                &names!($table)
+            ),
+            concat!(
+                "the ",
+                $js,
+                " table in interpreter_manually_opt.js does not match ",
+                stringify!($table)
+            )
+        );
+
+        const _: () = assert!(
+            minified_js_table_matches(INTERPRETER_OPT, &names!($table)),
+            concat!(
+                "interpreter_opt.js has no table that matches ",
+                stringify!($table),
+                ", regenerate it from interpreter.js"
+            )
+        );
+    };
+}
+
+check_table!(Element, "els");
+check_table!(Attribute, "attrs");
+check_table!(KnownNamespace, "nss");
+check_table!(Style, "styles");
+check_table!(SvgElement, "svgEls");
+check_table!(MathMlElement, "mathEls");
+check_table!(SvgAttribute, "svgAttrs");
+check_table!(MathMlAttribute, "mathAttrs");
+
+/// Checks that the js array that starts with `table` contains exactly the names in order
+const fn js_table_matches(js: &[u8], table: &[u8], names: &[&str]) -> bool {
+    match find(js, table) {
+        Some(pos) => array_matches(js, pos + table.len(), names),
+        None => false,
+    }
+}
+
+/// Checks that some array in the minified js contains exactly the names in order
+///
+/// The minifier renames the tables, so they are found by their contents instead of their names
+const fn minified_js_table_matches(js: &[u8], names: &[&str]) -> bool {
+    let mut pos = 0;
+    while pos < js.len() {
+        if js[pos] == b'[' && array_matches(js, pos + 1, names) {
+            return true;
+        }
+        pos += 1;
+    }
+    false
+}
+
+/// Checks that the elements of the js array starting after the `[` at `pos` are exactly the names in order
+///
+/// The elements can be string literals or variables that are initialized with a string literal, which is how the minifier stores strings that are used more than once
+const fn array_matches(js: &[u8], mut pos: usize, names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        if i > 0 {
+            pos = skip_whitespace(js, pos);
+            if pos >= js.len() || js[pos] != b',' {
+                return false;
+            }
+            pos += 1;
+        }
+        pos = skip_whitespace(js, pos);
+        let (start, end) = match string_at(js, pos) {
+            Some(string) => string,
+            None => return false,
+        };
+        if !bytes_eq(js, start, end, names[i].as_bytes()) {
+            return false;
+        }
+        pos = entry_end(js, pos);
+        i += 1;
+    }
+    // the table must not contain any extra names
+    pos = skip_whitespace(js, pos);
+    if pos < js.len() && js[pos] == b',' {
+        pos = skip_whitespace(js, pos + 1);
+    }
+    pos < js.len() && js[pos] == b']'
+}
+
+/// The range of the string that the string literal or variable at `pos` contains
+const fn string_at(js: &[u8], pos: usize) -> Option<(usize, usize)> {
+    if pos >= js.len() {
+        return None;
+    }
+    if is_quote(js[pos]) {
+        return string_literal(js, pos);
+    }
+    let end = entry_end(js, pos);
+    if end == pos {
+        return None;
+    }
+    // find the declaration of the variable in the first statement: `let name=` or `,name=` followed by a string literal
+    let name = subslice(js, pos, end);
+    let mut i = 0;
+    while i < js.len() && js[i] != b';' {
+        if is_quote(js[i]) {
+            i = entry_end(js, i);
+            continue;
+        }
+        if i > 0
+            && (js[i - 1] == b' ' || js[i - 1] == b',')
+            && i + name.len() + 1 < js.len()
+            && js[i + name.len()] == b'='
+            && is_quote(js[i + name.len() + 1])
+            && bytes_eq(js, i, i + name.len(), name)
+        {
+            return string_literal(js, i + name.len() + 1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// The range of the contents of the string literal that starts with the quote at `pos`
+const fn string_literal(js: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let quote = js[pos];
+    let mut end = pos + 1;
+    while end < js.len() {
+        if js[end] == quote {
+            return Some((pos + 1, end));
+        }
+        end += 1;
+    }
+    None
+}
+
+/// The end of the string literal or variable at `pos`
+const fn entry_end(js: &[u8], pos: usize) -> usize {
+    if pos < js.len() && is_quote(js[pos]) {
+        return match string_literal(js, pos) {
+            Some((_, end)) => end + 1,
+            None => js.len(),
+        };
+    }
+    let mut end = pos;
+    while end < js.len() && (js[end].is_ascii_alphanumeric() || js[end] == b'_' || js[end] == b'$')
+    {
+        end += 1;
+    }
+    end
+}
+
+const fn is_quote(b: u8) -> bool {
+    b == b'"' || b == b'\'' || b == b'`'
+}
+
+const fn skip_whitespace(js: &[u8], mut pos: usize) -> usize {
+    while pos < js.len() && js[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// Checks that `js[start..end]` is `bytes`
+const fn bytes_eq(js: &[u8], start: usize, end: usize, bytes: &[u8]) -> bool {
+    if end > js.len() || end - start != bytes.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if js[start + i] != bytes[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn subslice(js: &[u8], start: usize, end: usize) -> &[u8] {
+    js.split_at(end).0.split_at(start).1
+}
+
+/// Finds the first position of `needle` in `haystack`
+const fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let mut i = 0;
+    while i + needle.len() <= haystack.len() {
+        let mut j = 0;
+        while j < needle.len() && haystack[i + j] == needle[j] {
+            j += 1;
+        }
+        if j == needle.len() {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}